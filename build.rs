@@ -0,0 +1,49 @@
+//! Generates the module declarations and the solver table for `src/solutions`.
+//!
+//! Every `day_N.rs` in there gets a `mod day_N;` and its `REGISTRATION` (see the
+//! `solution!` macro) ends up in `SOLUTIONS`, so adding a day only needs the file itself.
+
+use std::{env, fmt::Write, fs, path::Path};
+
+fn main() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/solutions");
+    println!("cargo:rerun-if-changed={}", dir.display());
+
+    let mut days: Vec<usize> = fs::read_dir(&dir)
+        .expect("src/solutions should exist")
+        .filter_map(|e| {
+            let name = e.ok()?.file_name().into_string().ok()?;
+            name.strip_prefix("day_")?.strip_suffix(".rs")?.parse().ok()
+        })
+        .collect();
+    days.sort();
+
+    let mut out = String::new();
+    for d in &days {
+        assert!((1..=25).contains(d), "day_{d}.rs is not a day of advent");
+        let path = dir.join(format!("day_{d}.rs"));
+        writeln!(
+            out,
+            "#[path = {:?}]\nmod day_{d};",
+            path.display().to_string()
+        )
+        .unwrap();
+    }
+
+    writeln!(
+        out,
+        "\npub(crate) const SOLUTIONS: &[crate::registry::Solution] = &["
+    )
+    .unwrap();
+    for d in &days {
+        writeln!(
+            out,
+            "    crate::registry::Solution::new({d}, day_{d}::REGISTRATION),"
+        )
+        .unwrap();
+    }
+    writeln!(out, "];").unwrap();
+
+    let out_dir = env::var("OUT_DIR").expect("cargo sets OUT_DIR");
+    fs::write(Path::new(&out_dir).join("solutions.rs"), out).unwrap();
+}
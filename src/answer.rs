@@ -0,0 +1,141 @@
+use std::fmt::{self, Display};
+
+use serde::{Deserialize, Serialize};
+
+/// What a solver hands back for a part.
+///
+/// Most puzzles want a number, some want a word (day 5's crate letters) and some
+/// draw their answer as ASCII-art (day 10's CRT).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "lowercase")]
+pub enum Answer {
+    Integer(i64),
+    Text(String),
+    /// One string per row of pixels
+    Image(Vec<String>),
+}
+
+impl Answer {
+    /// Builds an [`Answer::Image`] from multi-line text, ignoring empty rows around it
+    pub fn image(screen: &str) -> Self {
+        let rows: Vec<String> = screen.lines().map(Into::into).collect();
+        let start = rows
+            .iter()
+            .position(|r| !r.is_empty())
+            .unwrap_or(rows.len());
+        let end = rows
+            .iter()
+            .rposition(|r| !r.is_empty())
+            .map_or(start, |e| e + 1);
+        Self::Image(rows[start..end].to_vec())
+    }
+
+    pub fn as_integer(&self) -> Option<i64> {
+        match self {
+            Answer::Integer(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Image(rows) if rows.len() > 1)
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(i) => write!(f, "{i}"),
+            Answer::Text(s) => write!(f, "{s}"),
+            Answer::Image(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    // no puzzle answer comes anywhere close to i64::MAX
+                    Answer::Integer(value as i64)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(i32, u32, i64, u64, isize, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+impl PartialEq<i64> for Answer {
+    fn eq(&self, other: &i64) -> bool {
+        self.as_integer() == Some(*other)
+    }
+}
+
+/// Compares the printed form, so an expected value read from a file matches any kind
+impl PartialEq<str> for Answer {
+    fn eq(&self, other: &str) -> bool {
+        let other = other.trim_end_matches('\n');
+        match self {
+            Answer::Integer(i) => other.parse() == Ok(*i),
+            Answer::Text(s) => s == other,
+            Answer::Image(rows) => rows.iter().map(String::as_str).eq(other.lines()),
+        }
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(Answer::from(42usize).to_string(), "42");
+        assert_eq!(Answer::from("CMZ").to_string(), "CMZ");
+        assert_eq!(Answer::image("\n#..\n.#.\n").to_string(), "#..\n.#.");
+    }
+
+    #[test]
+    fn compare() {
+        assert_eq!(Answer::from(24000u64), 24000);
+        assert_eq!(Answer::from(24000u64), "24000");
+        assert_eq!(Answer::from("MCD"), "MCD");
+        assert_eq!(Answer::image("##\n..\n"), "##\n..\n");
+        assert_ne!(Answer::from("24000"), 24000);
+    }
+
+    #[test]
+    fn json() {
+        let answers = [
+            Answer::from(-3isize),
+            Answer::from("RLFNRTNFB"),
+            Answer::image("#.\n.#"),
+        ];
+        let json = serde_json::to_string(&answers).unwrap();
+        assert_eq!(
+            json,
+            r##"[{"kind":"integer","value":-3},{"kind":"text","value":"RLFNRTNFB"},{"kind":"image","value":["#.",".#"]}]"##
+        );
+        let back: Vec<Answer> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, answers);
+    }
+}
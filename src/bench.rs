@@ -0,0 +1,221 @@
+//! Repeated timing of the solvers.
+
+use std::{
+    fmt::Write,
+    time::{Duration, Instant},
+};
+
+use serde::{Serialize, Serializer};
+
+use crate::{inputs::InputCache, solutions, AocError, Day, Part, Solution};
+
+#[derive(Debug, Clone, Copy)]
+pub struct BenchConfig {
+    /// Runs that are thrown away before measuring
+    pub warmup: usize,
+    pub iterations: usize,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warmup: 3,
+            iterations: 20,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Stats {
+    #[serde(rename = "min_ns", serialize_with = "nanos")]
+    pub min: Duration,
+    #[serde(rename = "median_ns", serialize_with = "nanos")]
+    pub median: Duration,
+    #[serde(rename = "mean_ns", serialize_with = "nanos")]
+    pub mean: Duration,
+    #[serde(rename = "stddev_ns", serialize_with = "nanos")]
+    pub stddev: Duration,
+    pub samples: usize,
+}
+
+fn nanos<S: Serializer>(d: &Duration, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_u128(d.as_nanos())
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        let n = samples.len();
+        if n == 0 {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        // the two middle samples are the same one if n is odd
+        let median = (sorted[(n - 1) / 2] + sorted[n / 2]) / 2;
+        let mean = sorted.iter().sum::<Duration>() / n as u32;
+        let variance = match n {
+            1 => 0.0,
+            _ => {
+                sorted
+                    .iter()
+                    .map(|d| (d.as_secs_f64() - mean.as_secs_f64()).powi(2))
+                    .sum::<f64>()
+                    / (n - 1) as f64
+            }
+        };
+
+        Some(Self {
+            min: sorted[0],
+            median,
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+            samples: n,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BenchResult {
+    #[serde(serialize_with = "day_number")]
+    pub day: Day,
+    #[serde(serialize_with = "part_number")]
+    pub part: Part,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+fn day_number<S: Serializer>(d: &Day, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_u64(**d as u64)
+}
+
+fn part_number<S: Serializer>(p: &Part, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_u64(*p as u64)
+}
+
+/// Times `cfg.iterations` parses of the input and as many solves of one parsed copy.
+///
+/// A failing run aborts the benchmark.
+pub fn bench_part(
+    solution: &Solution,
+    part: Part,
+    input: &str,
+    cfg: BenchConfig,
+) -> Result<BenchResult, AocError> {
+    let parse = sample(cfg, || solution.parse(input).map(drop))?;
+    let parsed = solution.parse(input)?;
+    let solve = sample(cfg, || solution.solve(&parsed, part).map(drop))?;
+
+    Ok(BenchResult {
+        day: solution.day(),
+        part,
+        parse,
+        solve,
+    })
+}
+
+fn sample(
+    cfg: BenchConfig,
+    mut f: impl FnMut() -> Result<(), AocError>,
+) -> Result<Stats, AocError> {
+    for _ in 0..cfg.warmup {
+        f()?;
+    }
+
+    let mut samples = Vec::with_capacity(cfg.iterations);
+    for _ in 0..cfg.iterations.max(1) {
+        let start = Instant::now();
+        f()?;
+        samples.push(start.elapsed());
+    }
+    Ok(Stats::from_samples(&samples).expect("at least one sample"))
+}
+
+/// Benchmarks the implemented parts of the selected days that have an input called `name`
+pub fn bench_all(
+    inputs: &InputCache,
+    name: &str,
+    cfg: BenchConfig,
+    filter: impl Fn(Day, Part) -> bool,
+) -> Vec<Result<BenchResult, AocError>> {
+    let mut results = vec![];
+    for solution in solutions() {
+        let Ok(input) = inputs.read(solution.day(), name) else {
+            continue;
+        };
+        for &part in solution.parts() {
+            if filter(solution.day(), part) {
+                results.push(bench_part(solution, part, &input, cfg));
+            }
+        }
+    }
+    results
+}
+
+pub fn to_csv(results: &[BenchResult]) -> String {
+    let mut csv = String::from("day,part,phase,min_ns,median_ns,mean_ns,stddev_ns,samples\n");
+    for r in results {
+        for (phase, s) in [("parse", &r.parse), ("solve", &r.solve)] {
+            writeln!(
+                csv,
+                "{},{},{phase},{},{},{},{},{}",
+                *r.day,
+                r.part as usize,
+                s.min.as_nanos(),
+                s.median.as_nanos(),
+                s.mean.as_nanos(),
+                s.stddev.as_nanos(),
+                s.samples
+            )
+            .unwrap();
+        }
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(v: &[u64]) -> Vec<Duration> {
+        v.iter().map(|&m| Duration::from_millis(m)).collect()
+    }
+
+    #[test]
+    fn stats() {
+        let s = Stats::from_samples(&ms(&[4, 2, 8, 6])).unwrap();
+        assert_eq!(s.min, Duration::from_millis(2));
+        assert_eq!(s.median, Duration::from_millis(5));
+        assert_eq!(s.mean, Duration::from_millis(5));
+        // sample standard deviation of 2, 4, 6, 8
+        assert_eq!(s.stddev.as_micros(), 2581);
+        assert_eq!(s.samples, 4);
+
+        let s = Stats::from_samples(&ms(&[3])).unwrap();
+        assert_eq!(
+            (s.median, s.stddev),
+            (Duration::from_millis(3), Duration::ZERO)
+        );
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn formats() {
+        let r = BenchResult {
+            day: Day(1),
+            part: Part::Part2,
+            parse: Stats::from_samples(&ms(&[2])).unwrap(),
+            solve: Stats::from_samples(&ms(&[1, 3])).unwrap(),
+        };
+        assert_eq!(
+            to_csv(std::slice::from_ref(&r)),
+            "day,part,phase,min_ns,median_ns,mean_ns,stddev_ns,samples\n\
+             1,2,parse,2000000,2000000,2000000,0,1\n\
+             1,2,solve,1000000,2000000,2000000,1414214,2\n"
+        );
+        assert_eq!(
+            serde_json::to_string(&r).unwrap(),
+            r#"{"day":1,"part":2,"parse":{"min_ns":2000000,"median_ns":2000000,"mean_ns":2000000,"stddev_ns":0,"samples":1},"solve":{"min_ns":1000000,"median_ns":2000000,"mean_ns":2000000,"stddev_ns":1414214,"samples":2}}"#
+        );
+    }
+}
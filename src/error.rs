@@ -0,0 +1,180 @@
+use thiserror::Error;
+
+use crate::{Day, Part};
+
+pub type SolveResult<T> = Result<T, SolveError>;
+
+/// Errors surfaced by [`crate::run_solver`] and the conversions of the CLI arguments.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum AocError {
+    #[error("invalid day: {0}")]
+    InvalidDay(String),
+    #[error("invalid part: {0}")]
+    InvalidPart(String),
+    #[error("day {day} part {part}{}: {msg}", fmt_line(.line))]
+    Solver {
+        day: usize,
+        part: usize,
+        line: Option<usize>,
+        msg: String,
+    },
+    #[error("day {day} input{}: {msg}", fmt_line(.line))]
+    Parse {
+        day: usize,
+        line: Option<usize>,
+        msg: String,
+    },
+    #[error("day {day} part {part} is not implemented yet")]
+    Unimplemented { day: usize, part: usize },
+    #[error("{path}: {msg}")]
+    File { path: String, msg: String },
+    #[error("invalid input name {0:?}, use letters, digits, '-' and '_'")]
+    InputName(String),
+}
+
+impl AocError {
+    pub fn file(path: &std::path::Path, err: impl ToString) -> Self {
+        Self::File {
+            path: path.display().to_string(),
+            msg: err.to_string(),
+        }
+    }
+
+    pub fn from_solve(day: Day, part: Part, err: SolveError) -> Self {
+        let (day, part) = (*day, part as usize);
+        match err {
+            SolveError::Input { line, msg } => Self::Solver {
+                day,
+                part,
+                line,
+                msg,
+            },
+            SolveError::Unimplemented => Self::Unimplemented { day, part },
+        }
+    }
+
+    /// Like [`AocError::from_solve`], for errors of the parse step both parts share
+    pub fn from_parse(day: Day, err: SolveError) -> Self {
+        let day = *day;
+        match err {
+            SolveError::Input { line, msg } => Self::Parse { day, line, msg },
+            SolveError::Unimplemented => Self::Parse {
+                day,
+                line: None,
+                msg: err.to_string(),
+            },
+        }
+    }
+}
+
+fn fmt_line(line: &Option<usize>) -> String {
+    line.map(|l| format!(" line {l}")).unwrap_or_default()
+}
+
+/// What a solver returns when it can't make sense of its input.
+///
+/// The solver doesn't know which day or part it's running as, the runner adds that
+/// when turning it into an [`AocError`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SolveError {
+    #[error("{msg}")]
+    Input { line: Option<usize>, msg: String },
+    #[error("not implemented")]
+    Unimplemented,
+}
+
+impl SolveError {
+    pub fn new(msg: impl ToString) -> Self {
+        Self::Input {
+            line: None,
+            msg: msg.to_string(),
+        }
+    }
+
+    /// `line` is 1-based, like an editor shows it
+    pub fn at(line: usize, msg: impl ToString) -> Self {
+        Self::new(msg).on_line(line)
+    }
+
+    /// Attaches a line number, unless a more specific one is already set
+    pub fn on_line(self, line: usize) -> Self {
+        match self {
+            Self::Input { line: None, msg } => Self::Input {
+                line: Some(line),
+                msg,
+            },
+            other => other,
+        }
+    }
+}
+
+impl From<&str> for SolveError {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<String> for SolveError {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<anyhow::Error> for SolveError {
+    fn from(value: anyhow::Error) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<nom::error::Error<&str>> for SolveError {
+    fn from(e: nom::error::Error<&str>) -> Self {
+        Self::new(format!("failed to parse {:?} ({:?})", e.input, e.code))
+    }
+}
+
+impl From<nom::Err<nom::error::Error<&str>>> for SolveError {
+    fn from(value: nom::Err<nom::error::Error<&str>>) -> Self {
+        match value {
+            nom::Err::Incomplete(_) => Self::new("incomplete input"),
+            nom::Err::Error(e) | nom::Err::Failure(e) => e.into(),
+        }
+    }
+}
+
+/// Runs `f` on every line, tagging errors with the line they came from.
+pub(crate) fn parse_lines<'a, T>(
+    input: &'a str,
+    mut f: impl FnMut(&'a str) -> SolveResult<T>,
+) -> SolveResult<Vec<T>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, l)| f(l).map_err(|e| e.on_line(i + 1)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_numbers() {
+        let r = parse_lines("1\n2\nx\n4", |l| l.parse::<u8>().map_err(SolveError::new));
+        assert_eq!(r, Err(SolveError::at(3, "invalid digit found in string")));
+    }
+
+    #[test]
+    fn keeps_inner_line() {
+        let e = SolveError::at(2, "inner").on_line(5);
+        assert_eq!(e, SolveError::at(2, "inner"));
+    }
+
+    #[test]
+    fn tagged_by_runner() {
+        let e = AocError::from_solve(Day(5), Part::Part2, SolveError::at(7, "bad move"));
+        assert_eq!(e.to_string(), "day 5 part 2 line 7: bad move");
+
+        let e = AocError::from_parse(Day(5), SolveError::at(3, "bad crate"));
+        assert_eq!(e.to_string(), "day 5 input line 3: bad crate");
+    }
+}
@@ -0,0 +1,358 @@
+//! Integer points and the directions between them.
+
+use derive_more::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A point, or the offset between two, in the plane. `y` grows downwards like the rows
+/// of a [`crate::grid::Grid`].
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Add,
+    AddAssign,
+    Sub,
+    SubAssign,
+    Neg,
+    Mul,
+)]
+pub struct Vec2<T = isize> {
+    pub x: T,
+    pub y: T,
+}
+
+/// Like [`Vec2`], with a third axis
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Add,
+    AddAssign,
+    Sub,
+    SubAssign,
+    Neg,
+    Mul,
+)]
+pub struct Vec3<T = isize> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Vec2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    /// Converts both coordinates, `None` if one of them doesn't fit, like a negative one
+    /// into `usize`
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<Vec2<U>> {
+        Some(Vec2::new(self.x.try_into().ok()?, self.y.try_into().ok()?))
+    }
+}
+
+impl<T> Vec3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    /// See [`Vec2::try_cast`]
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<Vec3<U>> {
+        Some(Vec3::new(
+            self.x.try_into().ok()?,
+            self.y.try_into().ok()?,
+            self.z.try_into().ok()?,
+        ))
+    }
+}
+
+impl<T> From<(T, T)> for Vec2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<Vec2<T>> for (T, T) {
+    fn from(Vec2 { x, y }: Vec2<T>) -> Self {
+        (x, y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Vec3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T> From<Vec3<T>> for (T, T, T) {
+    fn from(Vec3 { x, y, z }: Vec3<T>) -> Self {
+        (x, y, z)
+    }
+}
+
+impl Vec2<usize> {
+    /// Moved by a signed offset, `None` if that would leave the unsigned coordinates
+    pub fn checked_add_signed(self, d: Vec2<isize>) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_add_signed(d.x)?,
+            self.y.checked_add_signed(d.y)?,
+        ))
+    }
+}
+
+macro_rules! signed {
+    ($($t:ty),*) => {$(
+        impl Vec2<$t> {
+            pub fn abs(self) -> Self {
+                Self::new(self.x.abs(), self.y.abs())
+            }
+
+            /// Every coordinate as -1, 0 or 1, the step towards this offset in a king's move
+            pub fn signum(self) -> Self {
+                Self::new(self.x.signum(), self.y.signum())
+            }
+
+            /// Every coordinate limited to `min..=max`
+            pub fn clamp(self, min: $t, max: $t) -> Self {
+                Self::new(self.x.clamp(min, max), self.y.clamp(min, max))
+            }
+
+            /// The distance walking along the axes
+            pub fn manhattan(self) -> $t {
+                self.x.abs() + self.y.abs()
+            }
+
+            /// The distance walking diagonals as well
+            pub fn chebyshev(self) -> $t {
+                self.x.abs().max(self.y.abs())
+            }
+        }
+
+        impl Vec3<$t> {
+            /// The unit vectors along the axes, each positive one before its opposite
+            pub const UNITS: [Self; 6] = [
+                Self::new(1, 0, 0),
+                Self::new(-1, 0, 0),
+                Self::new(0, 1, 0),
+                Self::new(0, -1, 0),
+                Self::new(0, 0, 1),
+                Self::new(0, 0, -1),
+            ];
+
+            pub fn abs(self) -> Self {
+                Self::new(self.x.abs(), self.y.abs(), self.z.abs())
+            }
+
+            pub fn signum(self) -> Self {
+                Self::new(self.x.signum(), self.y.signum(), self.z.signum())
+            }
+
+            pub fn clamp(self, min: $t, max: $t) -> Self {
+                Self::new(
+                    self.x.clamp(min, max),
+                    self.y.clamp(min, max),
+                    self.z.clamp(min, max),
+                )
+            }
+
+            pub fn manhattan(self) -> $t {
+                self.x.abs() + self.y.abs() + self.z.abs()
+            }
+
+            pub fn chebyshev(self) -> $t {
+                self.x.abs().max(self.y.abs()).max(self.z.abs())
+            }
+
+            pub fn dot(self, other: Self) -> $t {
+                self.x * other.x + self.y * other.y + self.z * other.z
+            }
+        }
+    )*};
+}
+
+signed!(isize, i32, i64);
+
+/// The four directions along the axes
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction4 {
+    /// Clockwise from up
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    fn turned(self, quarters: usize) -> Self {
+        Self::ALL[(self as usize + quarters) % 4]
+    }
+
+    pub fn turn_right(self) -> Self {
+        self.turned(1)
+    }
+
+    pub fn turn_left(self) -> Self {
+        self.turned(3)
+    }
+
+    pub fn reverse(self) -> Self {
+        self.turned(2)
+    }
+
+    pub fn unit(self) -> Vec2 {
+        match self {
+            Self::Up => Vec2::new(0, -1),
+            Self::Right => Vec2::new(1, 0),
+            Self::Down => Vec2::new(0, 1),
+            Self::Left => Vec2::new(-1, 0),
+        }
+    }
+}
+
+/// The four directions along the axes and the four diagonals
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// Clockwise from up
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    fn turned(self, eighths: usize) -> Self {
+        Self::ALL[(self as usize + eighths) % 8]
+    }
+
+    /// An eighth of a turn clockwise
+    pub fn turn_right(self) -> Self {
+        self.turned(1)
+    }
+
+    pub fn turn_left(self) -> Self {
+        self.turned(7)
+    }
+
+    pub fn reverse(self) -> Self {
+        self.turned(4)
+    }
+
+    pub fn unit(self) -> Vec2 {
+        match self {
+            Self::Up => Vec2::new(0, -1),
+            Self::UpRight => Vec2::new(1, -1),
+            Self::Right => Vec2::new(1, 0),
+            Self::DownRight => Vec2::new(1, 1),
+            Self::Down => Vec2::new(0, 1),
+            Self::DownLeft => Vec2::new(-1, 1),
+            Self::Left => Vec2::new(-1, 0),
+            Self::UpLeft => Vec2::new(-1, -1),
+        }
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(d: Direction4) -> Self {
+        Self::ALL[d as usize * 2]
+    }
+}
+
+impl From<Direction4> for Vec2 {
+    fn from(d: Direction4) -> Self {
+        d.unit()
+    }
+}
+
+impl From<Direction8> for Vec2 {
+    fn from(d: Direction8) -> Self {
+        d.unit()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vectors() {
+        let a: Vec2 = Vec2::new(3, -4);
+        assert_eq!(a + Vec2::new(1, 1), Vec2::new(4, -3));
+        assert_eq!(-a * 2, Vec2::new(-6, 8));
+        assert_eq!((a.manhattan(), a.chebyshev()), (7, 4));
+        assert_eq!(a.signum(), Vec2::new(1, -1));
+        assert_eq!(a.clamp(-1, 1), Vec2::new(1, -1));
+
+        let b = Vec3::new(1i32, -2, 5);
+        assert_eq!((b.manhattan(), b.chebyshev()), (8, 5));
+        assert_eq!(b - b.signum(), Vec3::new(0, -1, 4));
+        assert_eq!(b.dot(Vec3::new(2, 1, 0)), 0);
+        for pair in Vec3::<i32>::UNITS.chunks(2) {
+            assert_eq!((pair[0], pair[0].manhattan()), (-pair[1], 1));
+        }
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Vec2::new(2isize, 3).try_cast(), Some(Vec2::new(2usize, 3)));
+        assert_eq!(Vec2::new(2isize, -3).try_cast::<usize>(), None);
+        assert_eq!(Vec2::new(usize::MAX, 0).try_cast::<isize>(), None);
+        assert_eq!(
+            Vec3::new(1usize, 2, 3).try_cast(),
+            Some(Vec3::new(1i32, 2, 3))
+        );
+
+        let p = Vec2::new(0usize, 5);
+        assert_eq!(
+            p.checked_add_signed(Vec2::new(1, -5)),
+            Some(Vec2::new(1, 0))
+        );
+        assert_eq!(p.checked_add_signed(Vec2::new(-1, 0)), None);
+    }
+
+    #[test]
+    fn directions() {
+        for d in Direction4::ALL {
+            assert_eq!(d.turn_left().turn_right(), d);
+            assert_eq!(d.reverse().unit(), -d.unit());
+            assert_eq!(Direction8::from(d).unit(), d.unit());
+            assert_eq!(d.unit().manhattan(), 1);
+        }
+        assert_eq!(Direction4::Left.turn_right(), Direction4::Up);
+        for d in Direction8::ALL {
+            assert_eq!(d.turn_right().turn_left(), d);
+            assert_eq!(d.reverse().unit(), -d.unit());
+            assert_eq!(d.unit().chebyshev(), 1);
+            // turning a quarter is two eighths
+            let quarter = d.turn_right().turn_right().unit();
+            assert_eq!(quarter, Vec2::new(-d.unit().y, d.unit().x));
+        }
+    }
+}
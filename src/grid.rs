@@ -0,0 +1,332 @@
+//! A rectangle of cells, the shape most puzzle inputs come in.
+
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use crate::{
+    geom::{Direction4, Direction8, Vec2},
+    SolveError, SolveResult,
+};
+
+/// Column and row, rows grow downwards
+pub type Pos = Vec2<usize>;
+
+/// A grid of cells stored row by row.
+///
+/// [`Grid::get`] and friends return `None` outside the grid, indexing with a [`Pos`]
+/// (or a `(x, y)` tuple) panics there like indexing a `Vec` does.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Cuts `cells` into rows of `width`
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        assert!(width > 0, "a grid needs columns");
+        assert_eq!(cells.len() % width, 0, "the last row is incomplete");
+        Self {
+            height: cells.len() / width,
+            width,
+            cells,
+        }
+    }
+
+    /// Reads one cell per character, every line is a row.
+    ///
+    /// Errors of `cell` are tagged with the line they came from.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> SolveResult<T>) -> SolveResult<Self> {
+        let width = input
+            .lines()
+            .next()
+            .ok_or("the grid is empty")?
+            .chars()
+            .count();
+        let mut cells = vec![];
+        for (i, l) in input.lines().enumerate() {
+            if l.chars().count() != width {
+                return Err(SolveError::at(i + 1, "all rows need the same length"));
+            }
+            for c in l.chars() {
+                cells.push(cell(c).map_err(|e| e.on_line(i + 1))?);
+            }
+        }
+        if width == 0 {
+            return Err("the grid is empty".into());
+        }
+        Ok(Self::from_vec(width, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, pos: impl Into<Pos>) -> bool {
+        let Vec2 { x, y } = pos.into();
+        x < self.width && y < self.height
+    }
+
+    pub fn is_border(&self, pos: impl Into<Pos>) -> bool {
+        let Vec2 { x, y } = pos.into();
+        x == 0 || y == 0 || x + 1 == self.width || y + 1 == self.height
+    }
+
+    /// Where `pos` is in the row by row storage
+    pub fn index_of(&self, pos: impl Into<Pos>) -> usize {
+        let Vec2 { x, y } = pos.into();
+        y * self.width + x
+    }
+
+    pub fn pos_of(&self, index: usize) -> Pos {
+        Vec2::new(index % self.width, index / self.width)
+    }
+
+    pub fn get(&self, pos: impl Into<Pos>) -> Option<&T> {
+        let pos = pos.into();
+        match self.in_bounds(pos) {
+            true => Some(&self.cells[self.index_of(pos)]),
+            false => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: impl Into<Pos>) -> Option<&mut T> {
+        let pos = pos.into();
+        match self.in_bounds(pos) {
+            true => {
+                let i = self.index_of(pos);
+                Some(&mut self.cells[i])
+            }
+            false => None,
+        }
+    }
+
+    /// The cells row by row
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    /// Every position, in the same order as [`Grid::iter`]
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Vec2::new(x, y)))
+    }
+
+    /// The first cell, row by row, that satisfies `f`
+    pub fn position(&self, f: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.cells.iter().position(f).map(|i| self.pos_of(i))
+    }
+
+    /// `pos` moved by `delta`, if that is still inside the grid
+    pub fn offset(&self, pos: impl Into<Pos>, delta: impl Into<Vec2>) -> Option<Pos> {
+        let pos = pos.into().checked_add_signed(delta.into())?;
+        self.in_bounds(pos).then_some(pos)
+    }
+
+    /// The up to 4 positions sharing an edge with `pos`, clockwise from above
+    pub fn neighbours4(&self, pos: impl Into<Pos>) -> impl Iterator<Item = Pos> + '_ {
+        let pos = pos.into();
+        Direction4::ALL
+            .into_iter()
+            .filter_map(move |d| self.offset(pos, d))
+    }
+
+    /// The up to 8 positions touching `pos`, diagonals included
+    pub fn neighbours8(&self, pos: impl Into<Pos>) -> impl Iterator<Item = Pos> + '_ {
+        let pos = pos.into();
+        Direction8::ALL
+            .into_iter()
+            .filter_map(move |d| self.offset(pos, d))
+    }
+
+    /// The positions seen looking from `pos` in `delta` steps, up to the edge of the grid
+    pub fn ray(
+        &self,
+        pos: impl Into<Pos>,
+        delta: impl Into<Vec2>,
+    ) -> impl Iterator<Item = Pos> + '_ {
+        let delta = delta.into();
+        std::iter::successors(self.offset(pos, delta), move |&p| self.offset(p, delta))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> std::slice::Chunks<'_, T> {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is outside the grid");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Adds a row at the bottom
+    pub fn push_row(&mut self, row: impl IntoIterator<Item = T>) {
+        let len = self.cells.len();
+        self.cells.extend(row);
+        assert_eq!(
+            self.cells.len() - len,
+            self.width,
+            "the row has the wrong length"
+        );
+        self.height += 1;
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Mirrored along the diagonal from the top left, rows become columns
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|x| self.column(x).cloned())
+            .collect();
+        Grid::from_vec(self.height, cells)
+    }
+
+    /// Turned a quarter clockwise, the left column becomes the top row
+    pub fn rotate(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|x| (0..self.height).rev().map(move |y| self[(x, y)].clone()))
+            .collect();
+        Grid::from_vec(self.height, cells)
+    }
+}
+
+impl<T, P: Into<Pos>> Index<P> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: P) -> &T {
+        let pos = pos.into();
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the grid"))
+    }
+}
+
+impl<T, P: Into<Pos>> IndexMut<P> for Grid<T> {
+    fn index_mut(&mut self, pos: P) -> &mut T {
+        let pos = pos.into();
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the grid"))
+    }
+}
+
+/// Every cell next to each other, one line per row
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn letters() -> Grid<char> {
+        Grid::parse("abc\ndef\n", Ok).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let g = letters();
+        assert_eq!((g.width(), g.height()), (3, 2));
+        assert_eq!(g[(2, 0)], 'c');
+        assert_eq!(g.get((0, 1)), Some(&'d'));
+        assert_eq!(g.get((3, 0)), None);
+        assert_eq!(g.position(|&c| c == 'e'), Some(Vec2::new(1, 1)));
+        assert_eq!(g.to_string(), "abc\ndef\n");
+
+        let e = Grid::parse("ab\nc\n", Ok).unwrap_err();
+        assert_eq!(e, SolveError::at(2, "all rows need the same length"));
+        let e = Grid::parse("12\n3x\n", |c| c.to_digit(10).ok_or("not a digit".into()));
+        assert_eq!(e.unwrap_err(), SolveError::at(2, "not a digit"));
+        assert!(Grid::parse("", Ok).is_err());
+    }
+
+    #[test]
+    fn neighbours() {
+        let g = letters();
+        let n: Vec<(usize, usize)> = g.neighbours4((0, 0)).map(Into::into).collect();
+        assert_eq!(n, [(1, 0), (0, 1)]);
+        let n: Vec<(usize, usize)> = g.neighbours8((1, 0)).map(Into::into).collect();
+        assert_eq!(n, [(2, 0), (2, 1), (1, 1), (0, 1), (0, 0)]);
+        assert_eq!(g.neighbours4((1, 1)).count(), 3);
+    }
+
+    #[test]
+    fn lines() {
+        let g = letters();
+        assert_eq!(g.row(1), ['d', 'e', 'f']);
+        let columns: Vec<String> = g.columns().map(|c| c.collect()).collect();
+        assert_eq!(columns, ["ad", "be", "cf"]);
+        let ray: Vec<char> = g.ray((0, 0), (1, 0)).map(|p| g[p]).collect();
+        assert_eq!(ray, ['b', 'c']);
+        assert_eq!(
+            g.ray((0, 0), Direction8::DownRight).collect::<Vec<_>>(),
+            [Vec2::new(1, 1)]
+        );
+        assert_eq!(g.ray((0, 0), Direction4::Left).count(), 0);
+    }
+
+    #[test]
+    fn reshape() {
+        let g = letters();
+        assert_eq!(g.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(g.rotate().to_string(), "da\neb\nfc\n");
+        assert_eq!(g.rotate().rotate().rotate().rotate(), g);
+        assert_eq!(g.transpose().transpose(), g);
+
+        let mut g = g.map(|c| c.to_ascii_uppercase());
+        g.push_row(['G', 'H', 'I']);
+        g[(0, 0)] = 'a';
+        assert_eq!(g.to_string(), "aBC\nDEF\nGHI\n");
+    }
+}
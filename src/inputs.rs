@@ -0,0 +1,238 @@
+//! Reading puzzle inputs and keeping a local cache of them.
+//!
+//! The cache is a plain directory (`input` by default). Every day can have several
+//! named inputs: the one named [`DEFAULT_INPUT`] is stored as `day_N`, any other name
+//! as `day_N.<name>`, e.g. `day_5.example`. Nothing here touches the network, inputs
+//! get in with [`InputCache::import`] or by copying files into the directory.
+
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use crate::{AocError, Day};
+
+/// The name of the input that is used when none is given
+pub const DEFAULT_INPUT: &str = "real";
+
+/// Where the default input of `day` is expected, `<dir>/day_N`
+pub fn input_path(dir: &Path, day: Day) -> PathBuf {
+    dir.join(format!("day_{}", *day))
+}
+
+/// Reads an input file, `-` reads stdin instead
+pub fn read(path: &Path) -> Result<String, AocError> {
+    let raw = if path == Path::new("-") {
+        let mut s = String::new();
+        std::io::stdin()
+            .read_to_string(&mut s)
+            .map_err(|e| AocError::file(path, e))?;
+        s
+    } else {
+        std::fs::read_to_string(path).map_err(|e| AocError::file(path, e))?
+    };
+    Ok(normalize(&raw))
+}
+
+/// Unix line endings, no trailing whitespace on any line and exactly one final newline.
+///
+/// Leading whitespace is kept, some drawings (day 5) depend on it.
+pub fn normalize(raw: &str) -> String {
+    let mut s = String::with_capacity(raw.len());
+    for l in raw.trim_end().lines() {
+        s.push_str(l.trim_end());
+        s.push('\n');
+    }
+    s
+}
+
+/// 64 bit FNV-1a of the input, stable across platforms and toolchains
+pub fn checksum(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf2_9ce4_8422_2325u64, |h, b| {
+        (h ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{hash:016x}")
+}
+
+/// An input found in the cache
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedInput {
+    pub day: Day,
+    pub name: String,
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Clone)]
+pub struct InputCache {
+    dir: PathBuf,
+}
+
+impl InputCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn path(&self, day: Day, name: &str) -> Result<PathBuf, AocError> {
+        check_name(name)?;
+        Ok(match name {
+            DEFAULT_INPUT => input_path(&self.dir, day),
+            _ => self.dir.join(format!("day_{}.{name}", *day)),
+        })
+    }
+
+    pub fn read(&self, day: Day, name: &str) -> Result<String, AocError> {
+        read(&self.path(day, name)?)
+    }
+
+    /// Copies `src` (`-` for stdin) into the cache, normalised.
+    ///
+    /// An existing input of the same name is only replaced with `force`.
+    pub fn import(
+        &self,
+        day: Day,
+        name: &str,
+        src: &Path,
+        force: bool,
+    ) -> Result<CachedInput, AocError> {
+        let path = self.path(day, name)?;
+        if !force && path.exists() {
+            return Err(AocError::file(
+                &path,
+                "already exists, use force to replace it",
+            ));
+        }
+        let input = read(src)?;
+        std::fs::create_dir_all(&self.dir).map_err(|e| AocError::file(&self.dir, e))?;
+        std::fs::write(&path, input).map_err(|e| AocError::file(&path, e))?;
+        Ok(CachedInput {
+            day,
+            name: name.to_string(),
+            path,
+        })
+    }
+
+    /// Every input in the cache, by day and then name. A missing directory is empty.
+    pub fn list(&self) -> Result<Vec<CachedInput>, AocError> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(AocError::file(&self.dir, e)),
+        };
+
+        let mut inputs = vec![];
+        for entry in entries {
+            let entry = entry.map_err(|e| AocError::file(&self.dir, e))?;
+            let Some(file_name) = entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+            if let Some((day, name)) = parse_file_name(&file_name) {
+                inputs.push(CachedInput {
+                    day,
+                    name: name.to_string(),
+                    path: entry.path(),
+                });
+            }
+        }
+        inputs.sort_by(|a, b| (a.day, &a.name).cmp(&(b.day, &b.name)));
+        Ok(inputs)
+    }
+}
+
+fn check_name(name: &str) -> Result<(), AocError> {
+    let valid = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+    if name.is_empty() || !name.chars().all(valid) {
+        return Err(AocError::InputName(name.to_string()));
+    }
+    Ok(())
+}
+
+/// `day_N` or `day_N.<name>`, anything else in the directory is ignored
+fn parse_file_name(file_name: &str) -> Option<(Day, &str)> {
+    let rest = file_name.strip_prefix("day_")?;
+    let (day, name) = match rest.split_once('.') {
+        // the default input has no suffix, `day_N.real` isn't read by anything
+        Some((_, DEFAULT_INPUT)) => return None,
+        Some(split) => split,
+        None => (rest, DEFAULT_INPUT),
+    };
+    let day = Day::try_from(day.parse::<usize>().ok()?).ok()?;
+    check_name(name).ok()?;
+    Some((day, name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_endings() {
+        assert_eq!(normalize("a \r\n\r\n  b\t\r\n\r\n"), "a\n\n  b\n");
+        assert_eq!(normalize("a\nb"), "a\nb\n");
+        assert_eq!(normalize(""), "");
+    }
+
+    #[test]
+    fn file_names() {
+        let cache = InputCache::new("input");
+        assert_eq!(
+            cache.path(Day(5), DEFAULT_INPUT).unwrap(),
+            Path::new("input/day_5")
+        );
+        assert_eq!(
+            cache.path(Day(5), "example").unwrap(),
+            Path::new("input/day_5.example")
+        );
+        assert_eq!(
+            cache.path(Day(5), "../x").unwrap_err(),
+            AocError::InputName("../x".into())
+        );
+
+        assert_eq!(parse_file_name("day_12"), Some((Day(12), "real")));
+        assert_eq!(
+            parse_file_name("day_3.alt-account"),
+            Some((Day(3), "alt-account"))
+        );
+        assert_eq!(parse_file_name("day_3.real"), None);
+        assert_eq!(parse_file_name("day_26"), None);
+        assert_eq!(parse_file_name("day_3.tar.gz"), None);
+    }
+
+    #[test]
+    fn checksums() {
+        assert_eq!(checksum(""), "cbf29ce484222325");
+        assert_eq!(checksum("a"), "af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn import_and_list() {
+        let dir = std::env::temp_dir().join(format!("aoc2022-inputs-{}", std::process::id()));
+        let cache = InputCache::new(&dir);
+        assert_eq!(cache.list(), Ok(vec![]));
+
+        let src = dir.with_extension("src");
+        std::fs::write(&src, "1\r\n2\r\n").unwrap();
+        cache.import(Day(2), "example", &src, false).unwrap();
+        cache.import(Day(1), DEFAULT_INPUT, &src, false).unwrap();
+        assert!(cache.import(Day(1), DEFAULT_INPUT, &src, false).is_err());
+        assert!(cache.import(Day(1), DEFAULT_INPUT, &src, true).is_ok());
+
+        let listed: Vec<_> = cache
+            .list()
+            .unwrap()
+            .into_iter()
+            .map(|i| (*i.day, i.name))
+            .collect();
+        assert_eq!(
+            listed,
+            [(1, "real".to_string()), (2, "example".to_string())]
+        );
+        assert_eq!(cache.read(Day(2), "example").unwrap(), "1\n2\n");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        std::fs::remove_file(&src).unwrap();
+    }
+}
@@ -3,50 +3,81 @@
 #![feature(int_roundings)]
 #![feature(exclusive_range_pattern)]
 
-use color_eyre::eyre;
-use eyre::{anyhow, Error};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+pub use answer::Answer;
+pub use error::{AocError, SolveError, SolveResult};
+pub use registry::{solutions, Solution};
+
+mod answer;
+pub mod bench;
+mod error;
+pub mod geom;
+pub mod grid;
+pub mod inputs;
+#[macro_use]
+mod registry;
+pub mod runner;
+mod solutions;
+pub mod verify;
 
-use solutions::*;
+static VERBOSE: AtomicBool = AtomicBool::new(false);
 
-mod solutions;
+/// Lets solvers print what they found along the way, on stderr so that the results on
+/// stdout stay readable for scripts. Off unless the CLI is run with `--verbose`.
+pub fn set_verbose(on: bool) {
+    VERBOSE.store(on, Ordering::Relaxed);
+}
 
-// should be done with macros or something
-const SOLVERS: &[&dyn Solver] = &[
-    &Day1, &Day2, &Day3, &Day4, &Day5, &Day6, &Day7, &Day8, &Day9, &Day10, &Day11, &Day12, &Day13,
-    &Day14, &Day15, &Day16, &Day17, &Day18, &Day19, &Day20, &Day21, &Day22, &Day23, &Day24, &Day25,
-];
+pub(crate) fn verbose() -> bool {
+    VERBOSE.load(Ordering::Relaxed)
+}
 
-pub fn run_solver(day: Day, part: Part, input: &str) -> String {
-    // assert!(day < SOLVERS.len() && day > 0);
-    // let day = day - 1;
+pub fn run_solver(day: Day, part: Part, input: &str) -> Result<Answer, AocError> {
+    registry::get(day)
+        .ok_or(AocError::Unimplemented {
+            day: *day,
+            part: part as usize,
+        })?
+        .run(part, input)
+}
 
-    SOLVERS[day.index()].run_part(input, part)
+/// Like [`run_solver`], on the input called `name` in the cache
+pub fn run_cached(
+    day: Day,
+    part: Part,
+    inputs: &inputs::InputCache,
+    name: &str,
+) -> Result<Answer, AocError> {
+    run_solver(day, part, &inputs.read(day, name)?)
 }
 
-pub enum ParsingErrors {
-    InvalidDay(String),
-    InvalidPart(String),
+/// Every day and part that has a solver behind it
+pub fn implemented() -> impl Iterator<Item = (Day, Part)> {
+    solutions()
+        .iter()
+        .flat_map(|s| s.parts().iter().map(|&p| (s.day(), p)))
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Part {
     Part1 = 1,
     Part2 = 2,
 }
 
 impl TryFrom<usize> for Part {
-    type Error = Error;
+    type Error = AocError;
 
     fn try_from(value: usize) -> Result<Self, Self::Error> {
         match value {
             1 => Ok(Part::Part1),
             2 => Ok(Part::Part2),
-            _ => Err(anyhow!("Part can only be 1 or 2")),
+            _ => Err(AocError::InvalidPart("Part can only be 1 or 2".into())),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Day(usize);
 
 impl Day {
@@ -56,13 +87,13 @@ impl Day {
 }
 
 impl TryFrom<usize> for Day {
-    type Error = Error;
+    type Error = AocError;
 
     fn try_from(value: usize) -> Result<Self, Self::Error> {
         match value {
-            0 => Err(anyhow!("So, day 0 you say?")),
+            0 => Err(AocError::InvalidDay("So, day 0 you say?".into())),
             x @ 1..=25 => Ok(Day(x)),
-            _ => Err(anyhow!("Missed Christmas this year?")),
+            _ => Err(AocError::InvalidDay("Missed Christmas this year?".into())),
         }
     }
 }
@@ -75,11 +106,18 @@ impl std::ops::Deref for Day {
     }
 }
 
+/// A day's solution, split into reading the input and solving the two parts.
+///
+/// The input is parsed once and shared by both parts, so parsing can be timed (and
+/// benchmarked) on its own.
 pub trait Solver {
-    fn part_1(&self, input: &str) -> String;
-    fn part_2(&self, input: &str) -> String;
+    type Input;
+
+    fn parse(&self, input: &str) -> SolveResult<Self::Input>;
+    fn part_1(&self, input: &Self::Input) -> SolveResult<Answer>;
+    fn part_2(&self, input: &Self::Input) -> SolveResult<Answer>;
 
-    fn run_part(&self, input: &str, part: Part) -> String {
+    fn run_part(&self, input: &Self::Input, part: Part) -> SolveResult<Answer> {
         match part {
             Part::Part1 => self.part_1(input),
             Part::Part2 => self.part_2(input),
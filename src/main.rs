@@ -1,38 +1,372 @@
-use std::path::PathBuf;
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
-use aoc2022::{run_solver, Day, Part};
-use clap::Parser;
+use aoc2022::{
+    bench::{self, BenchConfig, BenchResult, Stats},
+    inputs::{self, InputCache},
+    runner::{self, Record, Status, Summary},
+    solutions,
+    verify::{self, AnswerFile, Verdict},
+    AocError, Day, Part,
+};
+use clap::{Parser, Subcommand, ValueEnum};
+use color_eyre::eyre::eyre;
+use itertools::Itertools;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
+#[command(subcommand_negates_reqs = true)]
 struct Cli {
-    day: usize,
-    part: usize,
-    path: PathBuf,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[arg(required = true)]
+    day: Option<usize>,
+    #[arg(required = true)]
+    part: Option<usize>,
+    /// The input file, `-` for stdin [default: the cached input, see --input]
+    path: Option<PathBuf>,
+
+    /// The input cache, inputs are looked up there as `day_N` or `day_N.<NAME>`
+    #[arg(long, global = true, env = "AOC_INPUT_DIR", default_value = "input")]
+    input_dir: PathBuf,
+    /// Which of the cached inputs of a day to use
+    #[arg(long, global = true, default_value = inputs::DEFAULT_INPUT)]
+    input: String,
+
+    /// How runs are printed, bench has its own --report
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Plain)]
+    format: OutputFormat,
+    /// Let the solvers that can print what they found on stderr
+    #[arg(short, long, global = true)]
+    verbose: bool,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run every implemented day on its input and print a summary
+    RunAll,
+    /// List the registered days and which parts are implemented
+    List,
+    /// Run every implemented day and compare the results with the recorded answers
+    Verify {
+        #[arg(long, default_value = "answers.toml")]
+        answers: PathBuf,
+        /// Store the current results as the expected answers instead of comparing
+        #[arg(long)]
+        record: bool,
+    },
+    /// Manage the cached inputs
+    Inputs {
+        #[command(subcommand)]
+        command: InputsCommand,
+    },
+    /// Time every implemented part over several runs
+    Bench {
+        /// Only this day
+        #[arg(long)]
+        day: Option<usize>,
+        /// Only this part
+        #[arg(long)]
+        part: Option<usize>,
+        #[arg(long, default_value_t = BenchConfig::default().warmup)]
+        warmup: usize,
+        #[arg(short = 'n', long, default_value_t = BenchConfig::default().iterations)]
+        iterations: usize,
+        /// How the timings are printed
+        #[arg(long, value_enum, default_value_t = BenchFormat::Table)]
+        report: BenchFormat,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum InputsCommand {
+    /// List the cached inputs with their checksums
+    List,
+    /// Copy a file (`-` for stdin) into the cache under the name given by --input
+    Import {
+        day: usize,
+        file: PathBuf,
+        /// Replace an input that is already cached
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+/// How runs are reported, `json` and `tsv` print one record per part
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Plain,
+    Json,
+    Tsv,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+enum BenchFormat {
+    Table,
+    Csv,
+    Json,
 }
 
 fn main() -> color_eyre::Result<()> {
+    color_eyre::install()?;
+    let cli = Cli::parse();
+    aoc2022::set_verbose(cli.verbose);
+    let cache = InputCache::new(cli.input_dir);
+    let name = cli.input.as_str();
+
+    match (cli.command, cli.day, cli.part) {
+        (Some(Command::RunAll), ..) => print_records(&runner::run_all(&cache, name), cli.format),
+        (Some(Command::List), ..) => {
+            list();
+            Ok(())
+        }
+        (Some(Command::Verify { answers, record }), ..) => verify(&cache, name, &answers, record),
+        (Some(Command::Inputs { command }), ..) => manage_inputs(&cache, name, command),
+        (
+            Some(Command::Bench {
+                day,
+                part,
+                warmup,
+                iterations,
+                report,
+            }),
+            ..,
+        ) => {
+            let day: Option<Day> = day.map(Day::try_from).transpose()?;
+            let part: Option<Part> = part.map(Part::try_from).transpose()?;
+            let cfg = BenchConfig { warmup, iterations };
+            let results = bench::bench_all(&cache, name, cfg, |d, p| {
+                day.iter().all(|&day| day == d) && part.iter().all(|&part| part == p)
+            });
+            print_bench(results, report)
+        }
+        (None, Some(day), Some(part)) => run(day, part, cli.path, &cache, name, cli.format),
+        (None, ..) => Err(eyre!("a day and a part are needed without a subcommand")),
+    }
+}
+
+fn run(
+    day: usize,
+    part: usize,
+    path: Option<PathBuf>,
+    cache: &InputCache,
+    name: &str,
+    format: OutputFormat,
+) -> color_eyre::Result<()> {
     use std::time::Instant;
     let total = Instant::now();
 
-    color_eyre::install()?;
-    let cli = Cli::parse();
+    let day: Day = day.try_into()?;
+    let part: Part = part.try_into()?;
+
+    if format == OutputFormat::Plain {
+        println!("Day {} Part {}", *day, part as usize);
+    }
+
+    let input = match path {
+        Some(path) => inputs::read(&path)?,
+        None => cache.read(day, name)?,
+    };
+    let solution = solutions()
+        .iter()
+        .find(|s| s.day() == day)
+        .ok_or(AocError::Unimplemented {
+            day: *day,
+            part: part as usize,
+        })?;
 
-    let day: Day = cli.day.try_into()?;
-    let part: Part = cli.part.try_into()?;
+    let summary = Summary {
+        day,
+        part,
+        status: runner::timed(solution, &[part], &input).remove(0),
+    };
+    if format != OutputFormat::Plain {
+        print_records(std::slice::from_ref(&summary), format)?;
+    }
 
-    println!("Day {} Part {}", *day, part as usize);
+    match summary.status {
+        Status::Failed { error, .. } => return Err(error.into()),
+        Status::Solved { answer, timings } if format == OutputFormat::Plain => {
+            let elapsed = total.elapsed();
+            println!("{}", answer);
+            println!("parse: {:.2?}", timings.parse);
+            println!("solver: {:.2?}", timings.solve);
+            println!("Elapsed: {:.2?}", elapsed);
+        }
+        _ => {}
+    }
 
-    let input = std::fs::read_to_string(cli.path)?;
-    let inner = Instant::now();
+    Ok(())
+}
+
+fn print_records(summaries: &[Summary], format: OutputFormat) -> color_eyre::Result<()> {
+    let records: Vec<Record> = summaries.iter().map(Record::from).collect();
+    match format {
+        OutputFormat::Plain => print_summary(summaries),
+        OutputFormat::Tsv => print!("{}", runner::to_tsv(&records)),
+        OutputFormat::Json => {
+            for r in &records {
+                println!("{}", serde_json::to_string(r)?);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn verify(cache: &InputCache, name: &str, path: &Path, record: bool) -> color_eyre::Result<()> {
+    let mut answers = AnswerFile::load(path)?;
+    let summaries = runner::run_all(cache, name);
+
+    if record {
+        let n = answers.record(&summaries);
+        answers.save(path)?;
+        println!("recorded {n} answers in {}", path.display());
+        return Ok(());
+    }
+
+    let checks = verify::verify(&summaries, &answers);
+    for c in checks.iter().filter(|c| c.verdict != Verdict::Skipped) {
+        println!("Day {:2} Part {}: {}", *c.day, c.part as usize, c.verdict);
+    }
+
+    let count = |f: fn(&Verdict) -> bool| checks.iter().filter(|c| f(&c.verdict)).count();
+    let failed = count(Verdict::is_failure);
+    println!(
+        "{} passed, {failed} failed, {} missing, {} skipped",
+        count(|v| *v == Verdict::Pass),
+        count(|v| matches!(v, Verdict::Missing(_))),
+        count(|v| *v == Verdict::Skipped),
+    );
+
+    if failed > 0 {
+        return Err(eyre!("{failed} answers don't match {}", path.display()));
+    }
+    Ok(())
+}
 
-    let result = run_solver(day, part, &input);
+fn manage_inputs(cache: &InputCache, name: &str, command: InputsCommand) -> color_eyre::Result<()> {
+    match command {
+        InputsCommand::List => {
+            for i in cache.list()? {
+                let input = inputs::read(&i.path)?;
+                println!(
+                    "Day {:2} {:<12} {} {:>6} lines",
+                    *i.day,
+                    i.name,
+                    inputs::checksum(&input),
+                    input.lines().count()
+                );
+            }
+        }
+        InputsCommand::Import { day, file, force } => {
+            let imported = cache.import(day.try_into()?, name, &file, force)?;
+            let input = inputs::read(&imported.path)?;
+            println!("{} {}", imported.path.display(), inputs::checksum(&input));
+        }
+    }
+    Ok(())
+}
 
-    let inner = inner.elapsed();
-    let elapsed = total.elapsed();
-    println!("{}", result);
-    println!("solver: {:.2?}", inner);
-    println!("Elapsed: {:.2?}", elapsed);
+fn print_bench(
+    results: Vec<Result<BenchResult, AocError>>,
+    format: BenchFormat,
+) -> color_eyre::Result<()> {
+    let (results, errors): (Vec<_>, Vec<_>) = results.into_iter().partition_result();
+    for e in errors {
+        eprintln!("{e}");
+    }
 
+    match format {
+        BenchFormat::Csv => print!("{}", bench::to_csv(&results)),
+        BenchFormat::Json => println!("{}", serde_json::to_string_pretty(&results)?),
+        BenchFormat::Table => {
+            println!(
+                "{:>3} {:>4} {:>5} | {:>10} {:>10} {:>10} {:>10}",
+                "Day", "Part", "", "min", "median", "mean", "stddev"
+            );
+            let row = |r: &BenchResult, phase: &str, s: &Stats| {
+                println!(
+                    "{:>3} {:>4} {phase:>5} | {:>10.2?} {:>10.2?} {:>10.2?} {:>10.2?}",
+                    *r.day, r.part as usize, s.min, s.median, s.mean, s.stddev
+                )
+            };
+            for r in &results {
+                row(r, "parse", &r.parse);
+                row(r, "solve", &r.solve);
+            }
+        }
+    }
     Ok(())
 }
+
+fn list() {
+    for s in solutions() {
+        let parts = match s.parts() {
+            [] => "-".to_string(),
+            parts => parts.iter().map(|&p| p as usize).join(", "),
+        };
+        println!("Day {:2}: {}", *s.day(), parts);
+    }
+}
+
+fn cell(status: &Status) -> (String, String) {
+    match status {
+        Status::Solved { answer, timings } => {
+            let answer = if answer.is_multiline() {
+                "<image>".to_string()
+            } else {
+                answer.to_string()
+            };
+            (answer, format!("{:.2?}", timings.solve))
+        }
+        Status::Failed { timings, .. } => ("error".to_string(), format!("{:.2?}", timings.solve)),
+        Status::NoInput => ("no input".to_string(), String::new()),
+        Status::Unimplemented => ("-".to_string(), String::new()),
+    }
+}
+
+fn print_summary(summaries: &[Summary]) {
+    println!(
+        "{:>3} | {:>10} | {:<20} | {:>10} | {:<20} | {:>10}",
+        "Day", "parse", "Part 1", "time", "Part 2", "time"
+    );
+    println!("{}", "-".repeat(88));
+
+    let mut total = Duration::ZERO;
+    for (day, parts) in &summaries.iter().group_by(|s| s.day) {
+        let parts: Vec<_> = parts.collect();
+        // the parts share their parse, count it once
+        let parse = parts
+            .iter()
+            .find_map(|s| s.status.timings())
+            .map(|t| t.parse);
+        total += parse.unwrap_or_default();
+        total += parts
+            .iter()
+            .filter_map(|s| s.status.timings())
+            .map(|t| t.solve)
+            .sum::<Duration>();
+
+        let parse = parse.map(|p| format!("{p:.2?}")).unwrap_or_default();
+        let [(a1, t1), (a2, t2)] = [cell(&parts[0].status), cell(&parts[1].status)];
+        println!(
+            "{:>3} | {parse:>10} | {a1:<20} | {t1:>10} | {a2:<20} | {t2:>10}",
+            *day
+        );
+    }
+
+    println!("{}", "-".repeat(88));
+    println!("total solver time: {total:.2?}");
+
+    // a failed parse fails both parts with the same error
+    let errors = summaries.iter().filter_map(|s| match &s.status {
+        Status::Failed { error, .. } => Some(error),
+        _ => None,
+    });
+    for error in errors.dedup() {
+        eprintln!("{error}");
+    }
+}
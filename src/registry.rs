@@ -0,0 +1,166 @@
+use std::any::Any;
+
+use crate::{solutions::SOLUTIONS, Answer, AocError, Day, Part, SolveResult, Solver};
+
+/// Registers the solver of a `day_N.rs` file.
+///
+/// `solution!(Day)` claims both parts, `solution!(Day, parts = [Part1])` only the
+/// listed ones, so stubs can be told apart from real solvers without running them.
+macro_rules! solution {
+    ($solver:expr) => {
+        solution!($solver, parts = [Part1, Part2]);
+    };
+    ($solver:expr, parts = [$($part:ident),*]) => {
+        pub(crate) const REGISTRATION: crate::registry::Registration =
+            crate::registry::Registration {
+                solver: &$solver,
+                parts: &[$(crate::Part::$part),*],
+            };
+    };
+}
+
+/// The object safe side of [`Solver`], which lets days with different `Input`s share
+/// one table. The parsed input is passed around as `dyn Any`.
+pub(crate) trait DynSolver {
+    fn parse(&self, input: &str) -> SolveResult<Box<dyn Any>>;
+    fn solve(&self, input: &dyn Any, part: Part) -> SolveResult<Answer>;
+}
+
+impl<S: Solver> DynSolver for S
+where
+    S::Input: 'static,
+{
+    fn parse(&self, input: &str) -> SolveResult<Box<dyn Any>> {
+        Ok(Box::new(Solver::parse(self, input)?))
+    }
+
+    fn solve(&self, input: &dyn Any, part: Part) -> SolveResult<Answer> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .expect("input parsed by a different solver");
+        self.run_part(input, part)
+    }
+}
+
+/// What a day file hands to the registry, see [`solution!`]
+pub(crate) struct Registration {
+    pub(crate) solver: &'static dyn DynSolver,
+    pub(crate) parts: &'static [Part],
+}
+
+/// A registered day
+pub struct Solution {
+    day: Day,
+    solver: &'static dyn DynSolver,
+    parts: &'static [Part],
+}
+
+impl Solution {
+    pub(crate) const fn new(day: usize, r: Registration) -> Self {
+        Self {
+            day: Day(day),
+            solver: r.solver,
+            parts: r.parts,
+        }
+    }
+
+    pub fn day(&self) -> Day {
+        self.day
+    }
+
+    /// The parts that are actually solved, the others are still stubs
+    pub fn parts(&self) -> &'static [Part] {
+        self.parts
+    }
+
+    pub fn is_implemented(&self, part: Part) -> bool {
+        self.parts.contains(&part)
+    }
+
+    /// Parses the input once, it can then be solved for either part
+    pub fn parse(&self, input: &str) -> Result<Parsed, AocError> {
+        let input = self
+            .solver
+            .parse(input)
+            .map_err(|e| AocError::from_parse(self.day, e))?;
+        Ok(Parsed {
+            day: self.day,
+            input,
+        })
+    }
+
+    /// Solves a part on an input parsed by [`Solution::parse`] of the same day
+    pub fn solve(&self, parsed: &Parsed, part: Part) -> Result<Answer, AocError> {
+        assert_eq!(parsed.day, self.day, "input parsed for another day");
+        if !self.is_implemented(part) {
+            return Err(AocError::Unimplemented {
+                day: *self.day,
+                part: part as usize,
+            });
+        }
+        self.solver
+            .solve(parsed.input.as_ref(), part)
+            .map_err(|e| AocError::from_solve(self.day, part, e))
+    }
+
+    pub fn run(&self, part: Part, input: &str) -> Result<Answer, AocError> {
+        if !self.is_implemented(part) {
+            return Err(AocError::Unimplemented {
+                day: *self.day,
+                part: part as usize,
+            });
+        }
+        self.solve(&self.parse(input)?, part)
+    }
+}
+
+/// The input of a day after [`Solution::parse`]
+pub struct Parsed {
+    day: Day,
+    input: Box<dyn Any>,
+}
+
+impl std::fmt::Debug for Parsed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Parsed").field("day", &self.day).finish()
+    }
+}
+
+/// All days that have a `day_N.rs`, in order
+pub fn solutions() -> &'static [Solution] {
+    SOLUTIONS
+}
+
+pub fn get(day: Day) -> Option<&'static Solution> {
+    SOLUTIONS.iter().find(|s| s.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sorted_and_unique() {
+        let days: Vec<usize> = solutions().iter().map(|s| *s.day()).collect();
+        assert!(days.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn stubs_are_not_run() {
+        let day = get(Day(25)).unwrap();
+        assert!(day.is_implemented(Part::Part1));
+        assert!(!day.is_implemented(Part::Part2));
+        assert_eq!(
+            day.run(Part::Part2, "").unwrap_err(),
+            AocError::Unimplemented { day: 25, part: 2 }
+        );
+    }
+
+    #[test]
+    fn parsed_once() {
+        let day = get(Day(1)).unwrap();
+        let parsed = day.parse("1000\n2000\n\n4000\n\n5000\n6000").unwrap();
+        assert_eq!(day.solve(&parsed, Part::Part1).unwrap(), 11000);
+        assert_eq!(day.solve(&parsed, Part::Part2).unwrap(), 18000);
+    }
+}
@@ -0,0 +1,215 @@
+use std::{
+    fmt::Write,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+use crate::{inputs::InputCache, solutions, Answer, AocError, Day, Part, Solution};
+
+/// Time spent in the two phases of a run.
+///
+/// Both parts of a day share one parse, so its time shows up in both of them.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.solve
+    }
+}
+
+/// How running a single part went
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    Solved { answer: Answer, timings: Timings },
+    Failed { error: AocError, timings: Timings },
+    NoInput,
+    Unimplemented,
+}
+
+impl Status {
+    pub fn timings(&self) -> Option<Timings> {
+        match self {
+            Status::Solved { timings, .. } | Status::Failed { timings, .. } => Some(*timings),
+            Status::NoInput | Status::Unimplemented => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub day: Day,
+    pub part: Part,
+    pub status: Status,
+}
+
+/// Runs the given parts of a day on one shared parse of `input`, timing both phases
+pub fn timed(solution: &Solution, parts: &[Part], input: &str) -> Vec<Status> {
+    let start = Instant::now();
+    let parsed = solution.parse(input);
+    let parse = start.elapsed();
+
+    parts
+        .iter()
+        .map(|&part| {
+            let parsed = match &parsed {
+                Ok(parsed) => parsed,
+                Err(error) => {
+                    return Status::Failed {
+                        error: error.clone(),
+                        timings: Timings {
+                            parse,
+                            solve: Duration::ZERO,
+                        },
+                    }
+                }
+            };
+            let start = Instant::now();
+            let result = solution.solve(parsed, part);
+            let timings = Timings {
+                parse,
+                solve: start.elapsed(),
+            };
+            match result {
+                Ok(answer) => Status::Solved { answer, timings },
+                Err(error) => Status::Failed { error, timings },
+            }
+        })
+        .collect()
+}
+
+/// Runs both parts of every registered day on its input called `name`.
+///
+/// Days without such an input and parts that are still stubs are skipped, not run.
+pub fn run_all(inputs: &InputCache, name: &str) -> Vec<Summary> {
+    let mut summaries = vec![];
+    for solution in solutions() {
+        let input = inputs.read(solution.day(), name).ok();
+        let statuses = match &input {
+            Some(input) => timed(solution, solution.parts(), input),
+            None => vec![],
+        };
+        for part in [Part::Part1, Part::Part2] {
+            let position = solution.parts().iter().position(|&p| p == part);
+            let status = match position {
+                None => Status::Unimplemented,
+                Some(_) if input.is_none() => Status::NoInput,
+                Some(i) => statuses[i].clone(),
+            };
+            summaries.push(Summary {
+                day: solution.day(),
+                part,
+                status,
+            });
+        }
+    }
+    summaries
+}
+
+/// A [`Summary`] flattened for scripts, see [`to_tsv`] and the `json` output of the CLI
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Record {
+    pub day: usize,
+    pub part: usize,
+    /// `solved`, `failed`, `no_input` or `unimplemented`
+    pub status: &'static str,
+    pub answer: Option<String>,
+    pub parse_ns: Option<u128>,
+    pub solve_ns: Option<u128>,
+    pub total_ns: Option<u128>,
+    pub error: Option<String>,
+}
+
+impl From<&Summary> for Record {
+    fn from(s: &Summary) -> Self {
+        let (status, answer, error) = match &s.status {
+            Status::Solved { answer, .. } => ("solved", Some(answer.to_string()), None),
+            Status::Failed { error, .. } => ("failed", None, Some(error.to_string())),
+            Status::NoInput => ("no_input", None, None),
+            Status::Unimplemented => ("unimplemented", None, None),
+        };
+        let timings = s.status.timings();
+        Self {
+            day: *s.day,
+            part: s.part as usize,
+            status,
+            answer,
+            parse_ns: timings.map(|t| t.parse.as_nanos()),
+            solve_ns: timings.map(|t| t.solve.as_nanos()),
+            total_ns: timings.map(|t| t.total().as_nanos()),
+            error,
+        }
+    }
+}
+
+/// One line per record, multi-line answers have their newlines escaped as `\n`
+pub fn to_tsv(records: &[Record]) -> String {
+    let mut tsv = String::from("day\tpart\tstatus\tanswer\tparse_ns\tsolve_ns\ttotal_ns\terror\n");
+    let field = |s: &Option<String>| {
+        s.as_deref()
+            .unwrap_or_default()
+            .replace('\t', " ")
+            .replace('\n', "\\n")
+    };
+    let nanos = |n: Option<u128>| n.map(|n| n.to_string()).unwrap_or_default();
+    for r in records {
+        writeln!(
+            tsv,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            r.day,
+            r.part,
+            r.status,
+            field(&r.answer),
+            nanos(r.parse_ns),
+            nanos(r.solve_ns),
+            nanos(r.total_ns),
+            field(&r.error)
+        )
+        .unwrap();
+    }
+    tsv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records() {
+        let timings = Timings {
+            parse: Duration::from_micros(2),
+            solve: Duration::from_micros(5),
+        };
+        let summaries = [
+            Summary {
+                day: Day(10),
+                part: Part::Part2,
+                status: Status::Solved {
+                    answer: Answer::image("#.\n.#"),
+                    timings,
+                },
+            },
+            Summary {
+                day: Day(13),
+                part: Part::Part1,
+                status: Status::Unimplemented,
+            },
+        ];
+        let records: Vec<Record> = summaries.iter().map(Record::from).collect();
+
+        assert_eq!(
+            to_tsv(&records),
+            "day\tpart\tstatus\tanswer\tparse_ns\tsolve_ns\ttotal_ns\terror\n\
+             10\t2\tsolved\t#.\\n.#\t2000\t5000\t7000\t\n\
+             13\t1\tunimplemented\t\t\t\t\t\n"
+        );
+        assert_eq!(
+            serde_json::to_string(&records[0]).unwrap(),
+            r##"{"day":10,"part":2,"status":"solved","answer":"#.\n.#","parse_ns":2000,"solve_ns":5000,"total_ns":7000,"error":null}"##
+        );
+    }
+}
@@ -1,52 +1,64 @@
 use itertools::Itertools;
 use std::collections::BinaryHeap;
 
-use crate::Solver;
+use crate::{Answer, SolveError, SolveResult, Solver};
 
 pub struct Day;
 
+solution!(Day);
+
 impl Solver for Day {
-    fn part_1(&self, input: &str) -> String {
-        solve_1(input).to_string()
+    type Input = BinaryHeap<usize>;
+
+    fn parse(&self, input: &str) -> SolveResult<Self::Input> {
+        build_heap(input)
+    }
+
+    fn part_1(&self, heap: &Self::Input) -> SolveResult<Answer> {
+        Ok(solve_1(heap)?.into())
     }
 
-    fn part_2(&self, input: &str) -> String {
-        solve_2(input).to_string()
+    fn part_2(&self, heap: &Self::Input) -> SolveResult<Answer> {
+        Ok(solve_2(heap).into())
     }
 }
 
-fn build_heap(input: &str) -> BinaryHeap<usize> {
+fn build_heap(input: &str) -> SolveResult<BinaryHeap<usize>> {
     input
         .lines()
-        .group_by(|&l| !l.is_empty())
+        .enumerate()
+        .group_by(|(_, l)| !l.is_empty())
         .into_iter()
         .filter(|(a, _b)| *a)
-        .filter_map(|(_a, b)| b.filter_map(|l| l.parse::<usize>().ok()).sum1())
+        .map(|(_a, b)| {
+            b.map(|(i, l)| l.parse::<usize>().map_err(|e| SolveError::at(i + 1, e)))
+                .sum()
+        })
         .collect()
 }
 
-fn solve_1(input: &str) -> usize {
-    let mut heap = build_heap(input);
-    heap.pop().expect("Heap should not be empty")
+fn solve_1(heap: &BinaryHeap<usize>) -> SolveResult<usize> {
+    heap.peek()
+        .copied()
+        .ok_or_else(|| "no calories in the input".into())
 }
 
-fn solve_2(input: &str) -> usize {
-    let mut heap = build_heap(input);
-    heap.drain_sorted().take(3).sum()
+fn solve_2(heap: &BinaryHeap<usize>) -> usize {
+    heap.clone().drain_sorted().take(3).sum()
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{solve_1, solve_2};
+    use super::{build_heap, solve_1, solve_2};
 
     const TEST: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
 
     #[test]
     fn test_solve_1() {
-        assert_eq!(solve_1(TEST), 24000);
+        assert_eq!(solve_1(&build_heap(TEST).unwrap()), Ok(24000));
     }
     #[test]
     fn test_solve_2() {
-        assert_eq!(solve_2(TEST), 45000);
+        assert_eq!(solve_2(&build_heap(TEST).unwrap()), 45000);
     }
 }
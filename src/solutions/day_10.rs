@@ -1,17 +1,25 @@
 use core::fmt;
 use std::{collections::VecDeque, ops::Rem};
 
-use crate::Solver;
+use crate::{error::parse_lines, Answer, SolveResult, Solver};
 
 pub struct Day;
 
+solution!(Day);
+
 impl Solver for Day {
-    fn part_1(&self, input: &str) -> String {
-        solve_part_1(input).to_string()
+    type Input = VecDeque<Instruction>;
+
+    fn parse(&self, input: &str) -> SolveResult<Self::Input> {
+        Ok(parse_lines(input, |l| Ok(Instruction::parse(l)?.1))?.into())
+    }
+
+    fn part_1(&self, program: &Self::Input) -> SolveResult<Answer> {
+        Ok(solve_part_1(program).into())
     }
 
-    fn part_2(&self, input: &str) -> String {
-        draw_crt(input).screen
+    fn part_2(&self, program: &Self::Input) -> SolveResult<Answer> {
+        Ok(Answer::image(&draw_crt(program).screen))
     }
 }
 
@@ -73,18 +81,15 @@ impl Crt {
         let c = cpu.cycle.rem(40);
         let x = cpu.reg_x;
 
-        if c == 0 {
-            self.screen.push('\n');
+        // the sprite is three pixels wide, centred on x
+        if ((x - 1)..=(x + 1)).contains(&(c as isize)) {
+            self.screen.push('#');
+        } else {
+            self.screen.push('.');
         }
 
-        if x <= 0 {
-            self.screen.push('.');
-        } else {
-            if ((x - 1)..(x + 2)).contains(&(c as isize)) {
-                self.screen.push('#');
-            } else {
-                self.screen.push('.');
-            }
+        if c == 39 {
+            self.screen.push('\n');
         }
     }
 }
@@ -111,7 +116,7 @@ impl fmt::Debug for Cpu {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
-enum Instruction {
+pub enum Instruction {
     Noop,
     Addx(isize),
 }
@@ -149,37 +154,29 @@ fn parse_isize(i: &str) -> IResult<&str, isize> {
     combinator::map(nom::character::complete::i64, |n| n as _)(i)
 }
 
-fn solve_part_1(i: &str) -> isize {
-    let mut cpu = launch_program(i);
+fn solve_part_1(program: &VecDeque<Instruction>) -> isize {
+    let mut cpu = Cpu::new(program.clone());
     let mut signals = vec![];
-    while cpu.step() {
-        if signal_cycle(cpu.cycle) {
-            let signal = cpu.cycle as isize * cpu.reg_x;
-            signals.push(signal);
+    // the signal is taken during a cycle, before the instruction in flight completes
+    while cpu.current.is_some() {
+        let cycle = cpu.cycle + 1;
+        if signal_cycle(cycle) {
+            signals.push(cycle as isize * cpu.reg_x);
         }
+        cpu.step();
     }
-    signals.iter().sum1().unwrap()
+    signals.iter().sum()
 }
 
-fn launch_program(i: &str) -> Cpu {
-    let program = i
-        .lines()
-        .map(|l| Instruction::parse(l).unwrap().1)
-        .collect();
-    let mut cpu = Cpu::new(program);
-    cpu
-}
-
-fn draw_crt(i: &str) -> Crt {
-    let mut cpu = launch_program(i);
+fn draw_crt(program: &VecDeque<Instruction>) -> Crt {
+    let mut cpu = Cpu::new(program.clone());
     let mut crt = Crt::default();
     // crt.draw(&cpu);
 
-    loop {
+    // the last step finishes the last pixel, drawing after it would start a new row
+    while cpu.current.is_some() {
         crt.draw(&cpu);
-        if !cpu.step() {
-            break;
-        }
+        cpu.step();
     }
     crt
 }
@@ -230,7 +227,7 @@ mod tests {
 
     #[test]
     fn full_case() {
-        let signals = solve_part_1(CASE_2);
+        let signals = solve_part_1(&Day.parse(CASE_2).unwrap());
 
         let expected = vec![420, 1140, 1800, 2940, 2880, 3960];
 
@@ -242,7 +239,7 @@ mod tests {
 
     #[test]
     fn check_part_2() {
-        let screen = draw_crt(CASE_2).screen;
+        let screen = draw_crt(&Day.parse(CASE_2).unwrap()).screen;
 
         print!("{}", screen);
         println!("");
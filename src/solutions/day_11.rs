@@ -5,24 +5,32 @@ use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{multispace0, one_of},
-    combinator,
+    combinator::{self, all_consuming},
     multi::{many1, separated_list0, separated_list1},
     number::complete,
     sequence::{delimited, preceded, terminated, tuple},
     IResult,
 };
 
-use crate::Solver;
+use crate::{Answer, SolveResult, Solver};
 
 pub struct Day;
 
+solution!(Day);
+
 impl Solver for Day {
-    fn part_1(&self, input: &str) -> String {
-        solve_rounds(input, 20, 3).to_string()
+    type Input = Vec<Monkey>;
+
+    fn parse(&self, input: &str) -> SolveResult<Self::Input> {
+        parse_monkeys(input)
     }
 
-    fn part_2(&self, input: &str) -> String {
-        solve_rounds(input, 10000, 1).to_string()
+    fn part_1(&self, monkeys: &Self::Input) -> SolveResult<Answer> {
+        Ok(solve_rounds(monkeys, 20, 3)?.into())
+    }
+
+    fn part_2(&self, monkeys: &Self::Input) -> SolveResult<Answer> {
+        Ok(solve_rounds(monkeys, 10000, 1)?.into())
     }
 }
 
@@ -116,8 +124,8 @@ fn parse_false(i: &str) -> IResult<&str, usize> {
     )(i)
 }
 
-#[derive(Debug, PartialEq)]
-struct Monkey {
+#[derive(Debug, PartialEq, Clone)]
+pub struct Monkey {
     inspected: u64,
     items: Vec<u64>,
     op: Operation,
@@ -174,12 +182,21 @@ impl Monkey {
     }
 }
 
-fn solve_rounds(i: &str, rounds: usize, worry_div: u64) -> u64 {
-    let (_, mut monkeys) = parse_monkey_list(i).expect("something wrong with parsing");
-    let mut items: Vec<(usize, u64)> = vec![];
-    let divisor_product = monkeys.iter().map(|m| m.test).product();
+fn parse_monkeys(i: &str) -> SolveResult<Vec<Monkey>> {
+    let (_, monkeys) = all_consuming(parse_monkey_list)(i)?;
+    let monkey_count = monkeys.len();
+    if let Some(m) = monkeys
+        .iter()
+        .find(|m| m.test == 0 || m.yes >= monkey_count || m.no >= monkey_count)
+    {
+        return Err(format!("invalid monkey {m:?}").into());
+    }
+    Ok(monkeys)
+}
 
-    dbg!(divisor_product);
+fn solve_rounds(monkeys: &[Monkey], rounds: usize, worry_div: u64) -> SolveResult<u64> {
+    let mut monkeys = monkeys.to_vec();
+    let divisor_product = monkeys.iter().map(|m| m.test).product();
 
     for _ in 0..rounds {
         do_round(&mut monkeys, worry_div, divisor_product);
@@ -190,8 +207,8 @@ fn solve_rounds(i: &str, rounds: usize, worry_div: u64) -> u64 {
         .sorted_by(|b, a| a.inspected.cmp(&b.inspected))
         .tuples()
         .next()
-        .unwrap();
-    x.inspected * y.inspected
+        .ok_or("monkey business needs at least two monkeys")?;
+    Ok(x.inspected * y.inspected)
 }
 
 fn do_round(monkeys: &mut Vec<Monkey>, worry_div: u64, divisor_product: u64) {
@@ -245,7 +262,8 @@ Test: divisible by 17
   If false: throw to monkey 1";
     #[test]
     fn test_solver_part1() {
-        assert_eq!(solve_rounds(TEST_CASE, 20, 3), 10605)
+        let monkeys = parse_monkeys(TEST_CASE).unwrap();
+        assert_eq!(solve_rounds(&monkeys, 20, 3), Ok(10605))
     }
 
     #[test]
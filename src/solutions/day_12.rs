@@ -1,28 +1,33 @@
-extern crate derive_more;
-use derive_more::{Add, AddAssign, Sub, SubAssign};
-use itertools::Itertools;
-
-use crate::Solver;
+use crate::{
+    grid::{Grid, Pos},
+    Answer, SolveError, SolveResult, Solver,
+};
 
 pub struct Day;
 
+solution!(Day);
+
 impl Solver for Day {
-    fn part_1(&self, input: &str) -> String {
-        find_s_to_e(input).unwrap().to_string()
+    type Input = Heightmap;
+
+    fn parse(&self, input: &str) -> SolveResult<Self::Input> {
+        Heightmap::parse(input)
     }
 
-    fn part_2(&self, input: &str) -> String {
-        find_e_to_low(input).unwrap().to_string()
+    fn part_1(&self, g: &Self::Input) -> SolveResult<Answer> {
+        Ok(find_s_to_e(g)?.into())
+    }
+
+    fn part_2(&self, g: &Self::Input) -> SolveResult<Answer> {
+        Ok(find_e_to_low(g)?.into())
     }
 }
 
 #[derive(Debug)]
-struct Grid {
-    grid: Vec<Cell>,
-    width: usize,
-    height: usize,
-    start: GridPoint,
-    end: GridPoint,
+pub struct Heightmap {
+    cells: Grid<Cell>,
+    start: Pos,
+    end: Pos,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -42,169 +47,81 @@ impl Cell {
     }
 }
 
-impl From<u8> for Cell {
-    fn from(value: u8) -> Self {
+impl TryFrom<char> for Cell {
+    type Error = SolveError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            b'S' => Self::Start,
-            b'E' => Self::End,
-            x => Self::Any(x - b'a'),
+            'S' => Ok(Self::Start),
+            'E' => Ok(Self::End),
+            x @ 'a'..='z' => Ok(Self::Any(x as u8 - b'a')),
+            x => Err(format!("{:?} is not a valid height", x).into()),
         }
     }
 }
 
-#[derive(Clone, Copy, Debug, Add, AddAssign, Sub, SubAssign, PartialEq, Eq, Hash)]
-struct GridPoint {
-    x: usize,
-    y: usize,
-}
-
-impl GridPoint {
-    fn as_index(&self, (width, height): (usize, usize)) -> usize {
-        (self.y * width + self.x) as usize
-    }
-
-    fn from_index(index: usize, (width, height): (usize, usize)) -> Self {
-        let x = index.rem_euclid(width) as usize;
-        let y = index.div_floor(width) as usize;
-        (x, y).into()
-    }
-}
-
-impl From<(usize, usize)> for GridPoint {
-    fn from((x, y): (usize, usize)) -> Self {
-        Self { x, y }
-    }
-}
-
-impl Grid {
-    fn get_coord(&self, p: GridPoint) -> Option<&Cell> {
-        let i = p.as_index(self.dim());
-        self.grid.get(i)
-    }
-
-    fn get_coord_mut(&mut self, p: GridPoint) -> Option<&mut Cell> {
-        let i = p.as_index(self.dim());
-        self.grid.get_mut(i)
-    }
-
-    fn in_bounds(&self, p: GridPoint) -> bool {
-        p.x < self.width && p.y < self.height
-    }
-
-    fn is_border(&self, p: GridPoint) -> bool {
-        p.x == 0 || p.x == self.width - 1 || p.y == 0 || p.y == self.height - 1
+impl Heightmap {
+    fn height(&self, p: Pos) -> u8 {
+        self.cells[p].height()
     }
 
     fn print(&self) {
-        self.grid
-            .iter()
-            .map(|c| char::from(c.height() + b'a'))
-            .chunks(self.width)
-            .into_iter()
-            .for_each(|l| {
-                let s: String = l.collect();
-                println!("{s}")
-            })
+        print!("{}", self.cells.map(|c| char::from(c.height() + b'a')))
     }
 
-    fn get_rising_neighbours(&self, p: GridPoint) -> impl Iterator<Item = GridPoint> + '_ {
-        let deltas: [(isize, isize); 4] = [(-1, 0), (0, -1), (1, 0), (0, 1)];
-        let cur_height = self.get_coord(p).expect("current out of bounds!").height();
-
-        deltas.into_iter().filter_map(move |(dx, dy)| {
-            Some(GridPoint {
-                x: p.x.checked_add_signed(dx)?,
-                y: p.y.checked_add_signed(dy)?,
-            })
-            .filter(|&s| self.in_bounds(s))
-            .filter(|&s| {
-                let other_height = self.get_coord(s).expect("neighbour out of bounds").height();
-                other_height < cur_height + 2
-            })
-        })
+    fn get_rising_neighbours(&self, p: Pos) -> impl Iterator<Item = Pos> + '_ {
+        let cur_height = self.height(p);
+        self.cells
+            .neighbours4(p)
+            .filter(move |&s| self.height(s) < cur_height + 2)
     }
 
-    fn get_downing_neighbours(&self, p: GridPoint) -> impl Iterator<Item = GridPoint> + '_ {
-        let deltas: [(isize, isize); 4] = [(-1, 0), (0, -1), (1, 0), (0, 1)];
-        let cur_height = self.get_coord(p).expect("current out of bounds!").height();
-
-        deltas.into_iter().filter_map(move |(dx, dy)| {
-            Some(GridPoint {
-                x: p.x.checked_add_signed(dx)?,
-                y: p.y.checked_add_signed(dy)?,
-            })
-            .filter(|&s| self.in_bounds(s))
-            .filter(|&s| {
-                let other_height = self.get_coord(s).expect("neighbour out of bounds").height();
-                other_height + 2 > cur_height
-            })
-        })
+    fn get_downing_neighbours(&self, p: Pos) -> impl Iterator<Item = Pos> + '_ {
+        let cur_height = self.height(p);
+        self.cells
+            .neighbours4(p)
+            .filter(move |&s| self.height(s) + 2 > cur_height)
     }
 
-    fn dim(&self) -> (usize, usize) {
-        (self.width, self.height)
-    }
+    fn parse(input: &str) -> SolveResult<Self> {
+        let cells = Grid::parse(input, Cell::try_from)?;
+        let start = cells
+            .position(|&c| c == Cell::Start)
+            .ok_or("no start (S) in the grid")?;
+        let end = cells
+            .position(|&c| c == Cell::End)
+            .ok_or("no end (E) in the grid")?;
 
-    fn parse(input: &str) -> Option<Self> {
-        let width = input.lines().next().unwrap().len() as _;
-        let height = input.lines().count() as _;
-        let grid: Vec<Cell> = input
-            .lines()
-            .flat_map(|l| l.bytes())
-            .map(|c| Cell::from(c))
-            .collect();
-
-        let start = grid.iter().position(|&c| c == Cell::Start).unwrap();
-        let end = grid.iter().position(|&c| c == Cell::End).unwrap();
-
-        Some(Self {
-            grid,
-            width,
-            height,
-            start: GridPoint::from_index(start, (width, height)),
-            end: GridPoint::from_index(end, (width, height)),
-        })
+        Ok(Self { cells, start, end })
     }
 }
 
-use pathfinding::{directed::bfs::bfs, grid};
-
-use anyhow::{anyhow, Result};
-
-fn find_s_to_e(input: &str) -> Result<usize> {
-    let g = Grid::parse(input).ok_or(anyhow!("failed to parse grid"))?;
+use pathfinding::directed::bfs::bfs;
 
+fn find_s_to_e(g: &Heightmap) -> SolveResult<usize> {
     let end = g.start;
     let start = g.end;
 
     let result = bfs(
         &start,
-        |&p| g.get_downing_neighbours(p).collect::<Vec<GridPoint>>(),
+        |&p| g.get_downing_neighbours(p).collect::<Vec<Pos>>(),
         |&p| p == end,
     )
-    .ok_or(anyhow!("No path found!"))?;
+    .ok_or("No path found!")?;
 
     Ok(result.len() - 1)
 }
 
-fn find_e_to_low(input: &str) -> Result<usize> {
-    let g = Grid::parse(input).ok_or(anyhow!("failed to parse grid"))?;
-
+fn find_e_to_low(g: &Heightmap) -> SolveResult<usize> {
     // we search from highest point to lowest point at the border
     let start = g.end;
 
     let result = bfs(
         &start,
-        |&p| g.get_downing_neighbours(p).collect::<Vec<GridPoint>>(),
-        |&p| {
-            g.is_border(p)
-                && match g.get_coord(p) {
-                    Some(o) => o.height() == 0,
-                    None => false,
-                }
-        },
+        |&p| g.get_downing_neighbours(p).collect::<Vec<Pos>>(),
+        |&p| g.cells.is_border(p) && g.height(p) == 0,
     )
-    .ok_or(anyhow!("No path found!"))?;
+    .ok_or("No path found!")?;
 
     Ok(result.len() - 1)
     // Err(anyhow!("How did we end up here?"))
@@ -214,6 +131,7 @@ fn find_e_to_low(input: &str) -> Result<usize> {
 mod tests {
 
     use super::*;
+    use crate::geom::Vec2;
     use assert_ok::assert_ok;
 
     const INPUT: &str = "Sabqponm\n\
@@ -224,27 +142,25 @@ mod tests {
 
     #[test]
     fn test_parse() {
-        let g = Grid::parse(INPUT).unwrap();
+        let g = Heightmap::parse(INPUT).unwrap();
     }
 
     #[test]
     fn test_get_neighbours() {
-        let g = Grid::parse(INPUT).unwrap();
-        let v: Vec<GridPoint> = g.get_rising_neighbours(g.start).collect();
-        let correct = vec![GridPoint::from((0usize, 1usize)), (1, 0).into()];
-        dbg!(correct);
-        dbg!(v);
+        let g = Heightmap::parse(INPUT).unwrap();
+        let v: Vec<Pos> = g.get_rising_neighbours(g.start).collect();
+        assert_eq!(v, [Vec2::new(1, 0), Vec2::new(0, 1)]);
     }
 
     #[test]
     fn test_find_s_to_e() {
-        let result = assert_ok!(find_s_to_e(INPUT));
+        let result = assert_ok!(find_s_to_e(&Heightmap::parse(INPUT).unwrap()));
         assert_eq!(result, 31)
     }
 
     #[test]
     fn test_find_e_to_low() {
-        let result = assert_ok!(find_e_to_low(INPUT));
+        let result = assert_ok!(find_e_to_low(&Heightmap::parse(INPUT).unwrap()));
         assert_eq!(result, 29)
     }
 }
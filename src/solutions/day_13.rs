@@ -1,19 +1,184 @@
-use crate::Solver;
+use std::{cmp::Ordering, fmt};
+
+use itertools::Itertools;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    combinator::{self, all_consuming},
+    multi::separated_list0,
+    sequence::delimited,
+    IResult,
+};
+
+use crate::{error::parse_lines, Answer, SolveError, SolveResult, Solver};
 
 pub struct Day;
 
+solution!(Day);
+
 impl Solver for Day {
-    fn part_1(&self, input: &str) -> String {
-        todo!()
+    type Input = Vec<Packet>;
+
+    fn parse(&self, input: &str) -> SolveResult<Self::Input> {
+        let packets = parse_lines(input, |l| match l {
+            "" => Ok(None),
+            l => Ok(Some(all_consuming(Packet::parse)(l)?.1)),
+        })?;
+        let packets: Vec<Packet> = packets.into_iter().flatten().collect();
+        if !packets.len().is_multiple_of(2) {
+            return Err("packets should come in pairs".into());
+        }
+        Ok(packets)
+    }
+
+    fn part_1(&self, packets: &Self::Input) -> SolveResult<Answer> {
+        Ok(ordered_pairs(packets).into())
     }
 
-    fn part_2(&self, input: &str) -> String {
-        todo!()
+    fn part_2(&self, packets: &Self::Input) -> SolveResult<Answer> {
+        Ok(decoder_key(packets).into())
+    }
+}
+
+/// An integer or a list of packets, compared like the puzzle orders them: `3` and `[[3]]`
+/// are equal
+#[derive(Debug, Clone)]
+pub enum Packet {
+    Int(u32),
+    List(Vec<Packet>),
+}
+
+impl Packet {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        alt((
+            combinator::map(nom::character::complete::u32, Self::Int),
+            combinator::map(
+                delimited(tag("["), separated_list0(tag(","), Self::parse), tag("]")),
+                Self::List,
+            ),
+        ))(i)
+    }
+
+    /// `[[n]]`, the divider packets of part 2
+    fn divider(n: u32) -> Self {
+        Self::List(vec![Self::List(vec![Self::Int(n)])])
+    }
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Packet::Int(a), Packet::Int(b)) => a.cmp(b),
+            (Packet::List(a), Packet::List(b)) => a.cmp(b),
+            // a mixed pair compares the integer as a list holding only it
+            (Packet::Int(_), Packet::List(b)) => std::slice::from_ref(self).cmp(b),
+            (Packet::List(a), Packet::Int(_)) => a.as_slice().cmp(std::slice::from_ref(other)),
+        }
+    }
+}
+
+impl PartialEq for Packet {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Packet {}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Packet::Int(n) => write!(f, "{n}"),
+            Packet::List(l) => write!(f, "[{}]", l.iter().join(",")),
+        }
+    }
+}
+
+/// Sum of the 1-based indices of the pairs that are in the right order
+fn ordered_pairs(packets: &[Packet]) -> usize {
+    packets
+        .iter()
+        .tuples()
+        .positions(|(l, r)| l < r)
+        .map(|i| i + 1)
+        .sum()
+}
+
+/// Product of the 1-based positions of the dividers once all packets are sorted
+fn decoder_key(packets: &[Packet]) -> usize {
+    // no need to sort, a divider's position is the number of packets before it
+    let before = |d: &Packet| packets.iter().filter(|&p| p < d).count();
+    let (two, six) = (Packet::divider(2), Packet::divider(6));
+    (before(&two) + 1) * (before(&six) + 2)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = "";
+    const INPUT: &str = "[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]
+";
+
+    #[test]
+    fn round_trip() {
+        for l in INPUT.lines().filter(|l| !l.is_empty()) {
+            let (_, p) = all_consuming(Packet::parse)(l).unwrap();
+            assert_eq!(p.to_string(), l);
+        }
+    }
+
+    #[test]
+    fn order() {
+        let packets = Day.parse(INPUT).unwrap();
+        let ordered: Vec<bool> = packets.iter().tuples().map(|(l, r)| l < r).collect();
+        assert_eq!(
+            ordered,
+            [true, true, false, true, false, true, false, false]
+        );
+        assert_eq!(Packet::Int(3).cmp(&Packet::divider(3)), Ordering::Equal);
+        assert_eq!(Packet::Int(3), Packet::divider(3));
+        assert_ne!(Packet::Int(3), Packet::divider(2));
+    }
+
+    #[test]
+    fn parts() {
+        let packets = Day.parse(INPUT).unwrap();
+        assert_eq!(ordered_pairs(&packets), 13);
+        assert_eq!(decoder_key(&packets), 140);
+    }
+
+    #[test]
+    fn bad_packet() {
+        assert_eq!(
+            Day.parse("[1,2]\n[1,a]").unwrap_err(),
+            SolveError::at(2, "failed to parse \",a]\" (Tag)")
+        );
+    }
 }
@@ -1,119 +1,96 @@
 use std::fmt::Display;
 
-use crate::Solver;
+use crate::{
+    error::parse_lines,
+    geom::{Direction4, Direction8, Vec2},
+    grid::Grid,
+    Answer, SolveResult, Solver,
+};
 use anyhow::anyhow;
-use derive_more::{Add, AddAssign, Sub, SubAssign};
 use itertools::Itertools;
 use nom::bytes::complete::tag;
-use nom::combinator::map;
+use nom::combinator::{all_consuming, map};
 use nom::sequence::separated_pair;
 use nom::{self, Finish, IResult};
 pub struct Day;
 
+solution!(Day);
+
 impl Solver for Day {
-    fn part_1(&self, input: &str) -> String {
-        solve_part_1(input, false).to_string()
+    type Input = Cave;
+
+    fn parse(&self, input: &str) -> SolveResult<Self::Input> {
+        Cave::parse(input)
+    }
+
+    fn part_1(&self, g: &Self::Input) -> SolveResult<Answer> {
+        Ok(solve_part_1(g, false)?.into())
     }
 
-    fn part_2(&self, input: &str) -> String {
-        solve_part_2(input, false).to_string()
+    fn part_2(&self, g: &Self::Input) -> SolveResult<Answer> {
+        Ok(solve_part_2(g, false)?.into())
     }
 }
 
-fn solve_part_2(input: &str, print: bool) -> usize {
-    let mut g = Grid::parse(input).unwrap();
+fn solve_part_2(g: &Cave, print: bool) -> SolveResult<usize> {
+    let mut g = g.clone();
     g.add_floor();
-    while g.drop_sand() != Drop::TheStart {}
+    loop {
+        match g.drop_sand()? {
+            Drop::TheStart => break,
+            Drop::TheAbyss => return Err("the sand fell past the floor".into()),
+            Drop::Dropping => {}
+        }
+    }
     if print {
         println!("{}", g);
     }
-    g.dropped
+    Ok(g.dropped)
 }
 
-fn solve_part_1(input: &str, print: bool) -> usize {
-    let mut g = Grid::parse(input).unwrap();
-    while g.drop_sand() != Drop::TheAbyss {}
+fn solve_part_1(g: &Cave, print: bool) -> SolveResult<usize> {
+    let mut g = g.clone();
+    while g.drop_sand()? != Drop::TheAbyss {}
     if print {
         println!("{}", g);
     }
-    g.dropped
+    Ok(g.dropped)
 }
 
-const SAND_START: PosG = PosG { x: 500, y: 0 };
-#[derive(
-    Clone, Copy, Debug, Add, AddAssign, Sub, SubAssign, PartialEq, Eq, Hash, PartialOrd, Ord,
-)]
-struct PosG {
-    x: isize,
-    y: isize,
-}
-
-impl PosG {
-    fn as_index(&self, dim: PosG) -> usize {
-        (self.y * dim.x + self.x) as usize
-    }
-
-    fn from_index(index: usize, (width, height): (isize, isize)) -> Self {
-        let x = (index as isize).rem_euclid(width) as isize;
-        let y = (index as isize).div_floor(width) as isize;
-        (x, y).into()
-    }
+const SAND_START: Vec2 = Vec2::new(500, 0);
 
-    fn parse(input: &str) -> IResult<&str, Self> {
-        map(
-            separated_pair(complete_isize, tag(","), complete_isize),
-            |(x, y)| Self { x, y },
-        )(input)
-    }
+fn parse_point(input: &str) -> IResult<&str, Vec2> {
+    map(
+        separated_pair(complete_isize, tag(","), complete_isize),
+        |(x, y)| Vec2 { x, y },
+    )(input)
 }
 
-fn parse_path(input: &str) -> IResult<&str, Vec<PosG>> {
-    nom::multi::separated_list1(tag(" -> "), PosG::parse)(input)
+fn parse_path(input: &str) -> IResult<&str, Vec<Vec2>> {
+    nom::multi::separated_list1(tag(" -> "), parse_point)(input)
 }
 
 fn complete_isize(input: &str) -> IResult<&str, isize> {
     map(nom::character::complete::i64, |u| u as isize)(input)
 }
 
-impl From<(usize, usize)> for PosG {
-    fn from((x, y): (usize, usize)) -> Self {
-        let (x, y) = (x as isize, y as isize);
-        Self { x, y }
-    }
-}
-
-impl From<(isize, isize)> for PosG {
-    fn from((x, y): (isize, isize)) -> Self {
-        Self { x, y }
-    }
-}
-
-impl From<PosG> for (isize, isize) {
-    fn from(PosG { x, y }: PosG) -> Self {
-        (x, y)
-    }
-}
-
-#[derive(Debug)]
-struct Grid {
-    grid: Vec<Cell>,
-    dim: PosG,
-    paths: Vec<Vec<PosG>>,
-    backtrack: Vec<PosG>,
-    current: PosG,
+#[derive(Debug, Clone)]
+pub struct Cave {
+    cells: Grid<Cell>,
+    paths: Vec<Vec<Vec2>>,
+    backtrack: Vec<Vec2>,
+    current: Vec2,
     dropped: usize,
     offset: isize,
-    sand_start: PosG,
+    sand_start: Vec2,
 }
 
-impl Display for Grid {
+impl Display for Cave {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.grid
-            .chunks(self.dim.x as _)
-            .map(|c| c.iter().map(|c| char::from(*c)).collect::<String>())
-            .enumerate()
-            .for_each(|(i, l)| writeln!(f, "{}:{}", i, l).unwrap());
-
+        for (i, l) in self.cells.rows().enumerate() {
+            let l: String = l.iter().map(|c| char::from(*c)).collect();
+            writeln!(f, "{}:{}", i, l)?;
+        }
         Ok(())
     }
 }
@@ -140,25 +117,33 @@ enum Drop {
     Dropping,
 }
 
-impl Grid {
-    fn get_cell(&self, v: PosG) -> Option<&Cell> {
-        let index = v.as_index(self.dim);
-        self.grid.get(index)
+impl Cave {
+    fn get_cell(&self, v: Vec2) -> Option<&Cell> {
+        self.cells.get(v.try_cast()?)
     }
 
-    fn get_cell_mut(&mut self, v: PosG) -> Option<&mut Cell> {
-        let index = v.as_index(self.dim);
-        self.grid.get_mut(index)
+    fn get_cell_mut(&mut self, v: Vec2) -> Option<&mut Cell> {
+        self.cells.get_mut(v.try_cast()?)
+    }
+
+    fn set_cell(&mut self, v: Vec2, cell: Cell) -> SolveResult<()> {
+        *self
+            .get_cell_mut(v)
+            .ok_or_else(|| format!("{v:?} is outside the cave"))? = cell;
+        Ok(())
     }
 
-    fn get_cell_slice(&self, v: PosG, l: usize, offset: isize) -> Option<&[Cell]> {
-        let index = (v.as_index(self.dim) as isize + offset) as usize;
-        // &self.grid[index..index + l]
-        self.grid.get(index..index + l)
+    /// The cells below left, below and below right of `v`
+    fn get_cells_below(&self, v: Vec2) -> Option<&[Cell]> {
+        let Vec2 { x, y } = (v + Direction4::Down.unit()).try_cast::<usize>()?;
+        if y >= self.cells.height() {
+            return None;
+        }
+        self.cells.row(y).get(x.checked_sub(1)?..x + 2)
     }
 
     fn reset_sand(&mut self) {
-        self.grid
+        self.cells
             .iter_mut()
             .filter(|c| **c == Cell::Sand)
             .for_each(|c| *c = Cell::Air);
@@ -167,92 +152,83 @@ impl Grid {
         self.dropped = 0;
     }
 
-    fn drop_sand(&mut self) -> Drop {
-        let (a, b, c) =
-            if let Some(x) = self.get_cell_slice(self.current + (0isize, 1isize).into(), 3, -1) {
-                x.iter().tuples().next().unwrap()
-            } else {
-                return Drop::TheAbyss;
-            };
+    fn drop_sand(&mut self) -> SolveResult<Drop> {
+        let Some(&[a, b, c]) = self.get_cells_below(self.current) else {
+            return Ok(Drop::TheAbyss);
+        };
 
+        // anything but air blocks the sand
         use Cell::*;
         match (a, b, c) {
             (_, Air, _) => {
                 // free fall
-                *self.get_cell_mut(self.current).unwrap() = Cell::Air;
+                self.set_cell(self.current, Air)?;
                 self.current.y += 1;
-                *self.get_cell_mut(self.current).unwrap() = Cell::Sand;
+                self.set_cell(self.current, Sand)?;
             }
-            (Air, Rock | Sand, _) => {
+            (Air, _, _) => {
                 // drop left
-                *self.get_cell_mut(self.current).unwrap() = Cell::Air;
+                self.set_cell(self.current, Air)?;
                 self.backtrack.push(self.current);
-                self.current += (-1isize, 1isize).into();
-                *self.get_cell_mut(self.current).unwrap() = Cell::Sand;
+                self.current += Direction8::DownLeft.unit();
+                self.set_cell(self.current, Sand)?;
             }
-            (Rock | Sand, Rock | Sand, Air) => {
+            (_, _, Air) => {
                 // drop right
-                *self.get_cell_mut(self.current).unwrap() = Cell::Air;
+                self.set_cell(self.current, Air)?;
                 self.backtrack.push(self.current);
-                self.current += (1isize, 1isize).into();
-                *self.get_cell_mut(self.current).unwrap() = Cell::Sand;
+                self.current += Direction8::DownRight.unit();
+                self.set_cell(self.current, Sand)?;
             }
-            (Rock | Sand, Rock | Sand, Rock | Sand) => {
+            (_, _, _) => {
                 // bottom out
                 self.dropped += 1;
 
                 if self.current == self.sand_start {
-                    return Drop::TheStart;
+                    return Ok(Drop::TheStart);
                 }
                 self.current = self.backtrack.pop().unwrap_or(self.sand_start);
-                *self.get_cell_mut(self.current).unwrap() = Cell::Sand;
-            }
-            (_, _, _) => {
-                unimplemented!()
+                self.set_cell(self.current, Sand)?;
             }
         }
-        Drop::Dropping
+        Ok(Drop::Dropping)
     }
-    fn from_paths(mut paths: Vec<Vec<PosG>>) -> anyhow::Result<Self> {
-        let (xmin, xmax) = paths
-            .iter()
-            .flat_map(|p| p.iter())
+    fn from_paths(mut paths: Vec<Vec<Vec2>>) -> anyhow::Result<Self> {
+        // the sand source is part of the cave even when no rock is near it
+        let points = || paths.iter().flatten().chain([&SAND_START]);
+        let (xmin, xmax) = points()
             .map(|v| v.x)
             .minmax()
             .into_option()
             .ok_or(anyhow!("failed to find minmax"))?;
 
-        let (ymin, ymax) = paths
-            .iter()
-            .flat_map(|p| p.iter())
+        let (ymin, ymax) = points()
             .map(|v| v.y)
             .minmax()
             .into_option()
             .ok_or(anyhow!("failed to find minmax"))?;
 
-        let min = PosG::from((xmin, ymin));
-        let max = PosG::from((xmax, ymax));
+        let min = Vec2::new(xmin, ymin);
+        let max = Vec2::new(xmax, ymax);
 
         let y = max.y + 2; // this way we can already fit the floor
-        let x = max.x - min.x + 2 * y; // this would make it bigger than necessary I guess, max to the floor should be y. we'll see
+                           // the pile on the floor spreads y - 1 to either side of the source, and the cells
+                           // below its edges have to be in the cave as well
+        let x = max.x - min.x + 2 * y + 1;
 
         let offset = min.x - y;
 
-        let dim = PosG { x, y };
-
         for p in paths.iter_mut() {
             for e in p.iter_mut() {
                 e.x -= offset;
             }
         }
 
-        let capacity = (dim.x * dim.y).abs() as usize;
-        let grid = vec![Cell::Air; capacity];
+        let cells = Grid::new(x as usize, y as usize, Cell::Air);
 
-        let sand_start = SAND_START - PosG { x: offset, y: 0 };
-        let mut grid = Self {
-            grid,
-            dim,
+        let sand_start = SAND_START - Vec2::new(offset, 0);
+        let mut cave = Self {
+            cells,
             paths,
             backtrack: vec![],
             current: sand_start,
@@ -260,77 +236,45 @@ impl Grid {
             offset,
             sand_start,
         };
-        grid.draw_paths()?;
-        Ok(grid)
+        cave.draw_paths()?;
+        Ok(cave)
     }
 
     fn add_floor(&mut self) {
-        for _ in 0..self.dim.x {
-            self.grid.push(Cell::Rock)
-        }
+        self.cells.push_row(vec![Cell::Rock; self.cells.width()]);
     }
 
     fn draw_paths(&mut self) -> anyhow::Result<()> {
-        let Self {
-            grid, dim, paths, ..
-        } = self;
-        paths
-            .iter()
-            .map(|p| {
-                let path: &[PosG] = &p;
-                for (start, end) in path.iter().tuple_windows() {
-                    let (p_start, p_end) = [start, end]
-                        .into_iter()
-                        .sorted_by(|a, b| a.as_index(*dim).cmp(&b.as_index(*dim)))
-                        .tuples()
-                        .next()
-                        .unwrap();
-
-                    let diff = *p_end - *p_start;
-                    let x = 5;
-                    // let y = x.sig
-
-                    let s_index = p_start.as_index(*dim);
-
-                    let e_index = p_end.as_index(*dim);
-
-                    match diff.into() {
-                        (0, 0) => {
-                            anyhow::bail!("path length 0");
-                        }
-                        (x, 0) => {
-                            grid.iter_mut()
-                                .skip(s_index)
-                                .take((x.abs() as usize) + 1)
-                                .for_each(|field| *field = Cell::Rock);
-                        }
-                        (0, _y) => {
-                            grid.iter_mut()
-                                .enumerate()
-                                .skip(s_index)
-                                .step_by(dim.x as usize)
-                                .take_while(|(i, _f)| *i <= e_index)
-                                .for_each(|(_, field)| *field = Cell::Rock);
-                        }
-                        (_, _) => {
-                            return Err(anyhow!("only straight paths supported"));
-                        }
+        let Self { cells, paths, .. } = self;
+        for path in paths.iter() {
+            for (&start, &end) in path.iter().tuple_windows() {
+                let step = match end - start {
+                    Vec2 { x: 0, y: 0 } => anyhow::bail!("path length 0"),
+                    d if d.x != 0 && d.y != 0 => anyhow::bail!("only straight paths supported"),
+                    d => d.signum(),
+                };
+                let mut p = start;
+                loop {
+                    let cell = p.try_cast().and_then(|pos| cells.get_mut(pos));
+                    *cell.ok_or_else(|| anyhow!("{p:?} is outside the cave"))? = Cell::Rock;
+                    if p == end {
+                        break;
                     }
+                    p += step;
                 }
-                Ok(())
-            })
-            .all(|l| l.is_ok());
-        *self.get_cell_mut(self.sand_start).unwrap() = Cell::Start;
+            }
+        }
+        let start = self.sand_start;
+        *self
+            .get_cell_mut(start)
+            .ok_or_else(|| anyhow!("the sand source {start:?} is outside the cave"))? = Cell::Start;
         Ok(())
     }
 
-    fn parse(input: &str) -> anyhow::Result<Self> {
-        let p = input
-            .lines()
-            .filter_map(|l| parse_path(l).finish().ok().map(|(_, p)| p))
-            .collect();
+    fn parse(input: &str) -> SolveResult<Self> {
+        let p = parse_lines(input, |l| Ok(all_consuming(parse_path)(l).finish()?.1))?;
 
-        Grid::from_paths(p)
+        Ok(Cave::from_paths(p)?)
     }
 }
 
@@ -338,10 +282,14 @@ impl Grid {
 mod tests {
     const INPUT: &str = "498,4 -> 498,6 -> 496,6
 503,4 -> 502,4 -> 502,9 -> 494,9";
-    use super::{parse_path, solve_part_1, solve_part_2, Day, Drop, Grid, PosG};
+    use super::{parse_path, solve_part_1, solve_part_2, Cave, Day, Drop, Solver};
+    use crate::{
+        geom::Vec2,
+        inputs::{InputCache, DEFAULT_INPUT},
+    };
     use assert_ok::assert_ok;
 
-    fn path_input_parser(input: &str) -> Vec<Vec<PosG>> {
+    fn path_input_parser(input: &str) -> Vec<Vec<Vec2>> {
         input.lines().map(|l| parse_path(l).unwrap().1).collect()
     }
 
@@ -350,18 +298,18 @@ mod tests {
         let mut it = path_input_parser(INPUT).into_iter();
         assert_eq!(
             Some(vec![
-                PosG::from((498isize, 4)),
-                PosG::from((498isize, 6)),
-                PosG::from((496isize, 6)),
+                Vec2::new(498, 4),
+                Vec2::new(498, 6),
+                Vec2::new(496, 6),
             ]),
             it.next()
         );
         assert_eq!(
             Some(vec![
-                PosG::from((503isize, 4)),
-                PosG::from((502isize, 4)),
-                PosG::from((502isize, 9)),
-                PosG::from((494isize, 9)),
+                Vec2::new(503, 4),
+                Vec2::new(502, 4),
+                Vec2::new(502, 9),
+                Vec2::new(494, 9),
             ]),
             it.next()
         )
@@ -369,13 +317,13 @@ mod tests {
 
     #[test]
     fn part_1() {
-        let mut g = Grid::parse(INPUT).unwrap();
+        let mut g = Cave::parse(INPUT).unwrap();
         println!("{}", g);
         g.draw_paths().unwrap();
         println!("{}", g);
         let mut i = 0;
 
-        while g.drop_sand() != Drop::TheAbyss {
+        while g.drop_sand().unwrap() != Drop::TheAbyss {
             i += 1;
         }
         println!("{}", i);
@@ -386,13 +334,13 @@ mod tests {
     }
     #[test]
     fn part_2() {
-        let mut g = Grid::parse(INPUT).unwrap();
+        let mut g = Cave::parse(INPUT).unwrap();
         println!("{}", g);
         g.add_floor();
         g.draw_paths().unwrap();
         println!("{}", g);
         let mut i = 0;
-        while g.drop_sand() != Drop::TheStart {
+        while g.drop_sand().unwrap() != Drop::TheStart {
             i += 1;
         }
         println!("{}", g);
@@ -403,14 +351,31 @@ mod tests {
 
     #[test]
     fn part_1_solver() {
-        assert_eq!(solve_part_1(INPUT, true), 24);
-        let input = include_str!("../../input/day_14");
-        assert_eq!(solve_part_1(input, true), 825);
+        let g = Cave::parse(INPUT).unwrap();
+        assert_eq!(solve_part_1(&g, true), Ok(24));
     }
     #[test]
     fn part_2_solver() {
-        assert_eq!(solve_part_2(INPUT, true), 93);
-        let input = include_str!("../../input/day_14");
-        assert_eq!(solve_part_2(input, true), 26729);
+        let g = Cave::parse(INPUT).unwrap();
+        assert_eq!(solve_part_2(&g, true), Ok(93));
+    }
+
+    #[test]
+    fn far_from_the_source() {
+        // the rock is nowhere near x=500, the sand falls past it or piles up on the floor
+        let g = Day.parse("10,1 -> 10,2\n").unwrap();
+        assert_eq!(Day.part_1(&g), Ok(0usize.into()));
+        assert_eq!(Day.part_2(&g), Ok(16usize.into()));
+    }
+
+    /// Needs the real input in the cache, `verify` checks the same answers
+    #[test]
+    #[ignore]
+    fn real_input() {
+        let cache = InputCache::new(concat!(env!("CARGO_MANIFEST_DIR"), "/input"));
+        let input = cache.read(crate::Day(14), DEFAULT_INPUT).unwrap();
+        let g = Cave::parse(&input).unwrap();
+        assert_eq!(solve_part_1(&g, true), Ok(825));
+        assert_eq!(solve_part_2(&g, true), Ok(26729));
     }
 }
@@ -1,19 +1,255 @@
-use crate::Solver;
+use itertools::Itertools;
+use nom::{
+    bytes::complete::tag,
+    combinator::all_consuming,
+    sequence::{preceded, separated_pair},
+    IResult,
+};
 
-pub struct Day;
+use crate::{error::parse_lines, Answer, SolveResult, Solver};
+
+pub struct Day {
+    /// The row scanned in part 1
+    row: i64,
+    /// The distress beacon has both coordinates in `0..=bound`
+    bound: i64,
+}
+
+solution!(Day {
+    row: 2_000_000,
+    bound: 4_000_000
+});
 
 impl Solver for Day {
-    fn part_1(&self, input: &str) -> String {
-        todo!()
+    type Input = Vec<Sensor>;
+
+    fn parse(&self, input: &str) -> SolveResult<Self::Input> {
+        parse_lines(input, |l| Ok(all_consuming(Sensor::parse)(l)?.1))
+    }
+
+    fn part_1(&self, sensors: &Self::Input) -> SolveResult<Answer> {
+        Ok(covered_in_row(sensors, self.row).into())
+    }
+
+    fn part_2(&self, sensors: &Self::Input) -> SolveResult<Answer> {
+        let (x, y) = find_beacon(sensors, self.bound).ok_or("no spot left for the beacon")?;
+        Ok((x * 4_000_000 + y).into())
+    }
+}
+
+type Pos = (i64, i64);
+
+fn manhattan(a: Pos, b: Pos) -> i64 {
+    (a.0 - b.0).abs() + (a.1 - b.1).abs()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sensor {
+    pos: Pos,
+    beacon: Pos,
+    /// Distance to the closest beacon, nothing closer can be a beacon
+    radius: i64,
+}
+
+impl Sensor {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        let (i, pos) = preceded(tag("Sensor at "), parse_pos)(i)?;
+        let (i, beacon) = preceded(tag(": closest beacon is at "), parse_pos)(i)?;
+        Ok((
+            i,
+            Self {
+                pos,
+                beacon,
+                radius: manhattan(pos, beacon),
+            },
+        ))
+    }
+
+    fn covers(&self, p: Pos) -> bool {
+        manhattan(self.pos, p) <= self.radius
     }
 
-    fn part_2(&self, input: &str) -> String {
-        todo!()
+    /// The columns this sensor covers in row `y`
+    fn row_coverage(&self, y: i64) -> Option<(i64, i64)> {
+        let reach = self.radius - (y - self.pos.1).abs();
+        (reach >= 0).then_some((self.pos.0 - reach, self.pos.0 + reach))
     }
 }
 
+fn parse_pos(i: &str) -> IResult<&str, Pos> {
+    let coord = nom::character::complete::i64;
+    separated_pair(
+        preceded(tag("x="), coord),
+        tag(", "),
+        preceded(tag("y="), coord),
+    )(i)
+}
+
+/// A set of integers, stored as sorted, disjoint and non-adjacent inclusive ranges
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct IntervalSet {
+    ranges: Vec<(i64, i64)>,
+}
+
+impl IntervalSet {
+    /// Adds `lo..=hi`, merging it with the ranges it overlaps or touches
+    pub fn insert(&mut self, lo: i64, hi: i64) {
+        if lo > hi {
+            return;
+        }
+        let start = self.ranges.partition_point(|&(_, h)| h < lo - 1);
+        let end = self.ranges.partition_point(|&(l, _)| l <= hi + 1);
+        let (lo, hi) = match &self.ranges[start..end] {
+            [] => (lo, hi),
+            merged => (lo.min(merged[0].0), hi.max(merged[merged.len() - 1].1)),
+        };
+        self.ranges.splice(start..end, [(lo, hi)]);
+    }
+
+    /// Takes `lo..=hi` out, splitting a range that only partly overlaps it
+    pub fn remove(&mut self, lo: i64, hi: i64) {
+        if lo > hi {
+            return;
+        }
+        let start = self.ranges.partition_point(|&(_, h)| h < lo);
+        let end = self.ranges.partition_point(|&(l, _)| l <= hi);
+        let kept: Vec<_> = self.ranges[start..end]
+            .iter()
+            .flat_map(|&(l, h)| [(l, lo - 1), (hi + 1, h)])
+            .filter(|&(l, h)| l <= h)
+            .collect();
+        self.ranges.splice(start..end, kept);
+    }
+
+    pub fn union(&mut self, other: &IntervalSet) {
+        for &(lo, hi) in &other.ranges {
+            self.insert(lo, hi);
+        }
+    }
+
+    pub fn contains(&self, x: i64) -> bool {
+        let i = self.ranges.partition_point(|&(_, h)| h < x);
+        self.ranges.get(i).is_some_and(|&(l, _)| l <= x)
+    }
+
+    /// How many integers are in the set
+    pub fn len(&self) -> u64 {
+        self.ranges.iter().map(|&(l, h)| (h - l + 1) as u64).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn ranges(&self) -> &[(i64, i64)] {
+        &self.ranges
+    }
+}
+
+/// Positions in row `y` that can't hold a beacon
+fn covered_in_row(sensors: &[Sensor], y: i64) -> u64 {
+    let mut row = IntervalSet::default();
+    for r in sensors.iter().filter_map(|s| s.row_coverage(y)) {
+        row.insert(r.0, r.1);
+    }
+    for s in sensors.iter().filter(|s| s.beacon.1 == y) {
+        row.remove(s.beacon.0, s.beacon.0);
+    }
+    row.len()
+}
+
+/// The one position in the square `0..=bound` no sensor covers.
+///
+/// It's a corner of the uncovered area, so it lies where the lines just outside the
+/// sensors' diamonds cross each other or the square's edges. Only those are checked.
+fn find_beacon(sensors: &[Sensor], bound: i64) -> Option<Pos> {
+    // x + y = sum and y - x = diff for the four edges just outside each diamond
+    let sums: Vec<i64> = sensors
+        .iter()
+        .flat_map(|s| [-1, 1].map(|d| s.pos.0 + s.pos.1 + d * (s.radius + 1)))
+        .unique()
+        .collect();
+    let diffs: Vec<i64> = sensors
+        .iter()
+        .flat_map(|s| [-1, 1].map(|d| s.pos.1 - s.pos.0 + d * (s.radius + 1)))
+        .unique()
+        .collect();
+
+    let crossings = sums
+        .iter()
+        .cartesian_product(&diffs)
+        .filter(|(s, d)| (*s + *d) % 2 == 0)
+        .map(|(s, d)| ((s - d) / 2, (s + d) / 2));
+    let on_edges = [0, bound].into_iter().flat_map(|e| {
+        let sums = sums.iter().flat_map(move |s| [(e, s - e), (s - e, e)]);
+        let diffs = diffs.iter().flat_map(move |d| [(e, d + e), (e - d, e)]);
+        sums.chain(diffs)
+    });
+    let corners = [(0, 0), (0, bound), (bound, 0), (bound, bound)];
+
+    crossings
+        .chain(on_edges)
+        .chain(corners)
+        .filter(|&(x, y)| (0..=bound).contains(&x) && (0..=bound).contains(&y))
+        .find(|&p| !sensors.iter().any(|s| s.covers(p)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = "";
+
+    const INPUT: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3
+";
+
+    const EXAMPLE: Day = Day { row: 10, bound: 20 };
+
+    #[test]
+    fn intervals() {
+        let mut set = IntervalSet::default();
+        set.insert(5, 8);
+        set.insert(0, 2);
+        set.insert(3, 3);
+        assert_eq!(set.ranges(), [(0, 3), (5, 8)]);
+        set.insert(4, 4);
+        assert_eq!(set.ranges(), [(0, 8)]);
+
+        set.remove(2, 3);
+        set.remove(8, 20);
+        assert_eq!(set.ranges(), [(0, 1), (4, 7)]);
+        assert_eq!(set.len(), 6);
+        assert!(set.contains(4) && !set.contains(2));
+
+        let mut other = IntervalSet::default();
+        other.insert(-3, 0);
+        other.insert(10, 10);
+        set.union(&other);
+        assert_eq!(set.ranges(), [(-3, 1), (4, 7), (10, 10)]);
+    }
+
+    #[test]
+    fn part_1() {
+        let sensors = EXAMPLE.parse(INPUT).unwrap();
+        assert_eq!(covered_in_row(&sensors, 10), 26);
+        assert_eq!(EXAMPLE.part_1(&sensors), Ok(26.into()));
+    }
+
+    #[test]
+    fn part_2() {
+        let sensors = EXAMPLE.parse(INPUT).unwrap();
+        assert_eq!(find_beacon(&sensors, 20), Some((14, 11)));
+        assert_eq!(EXAMPLE.part_2(&sensors), Ok(56000011.into()));
+    }
 }
@@ -1,19 +1,408 @@
-use crate::Solver;
+use std::{cmp::Reverse, collections::HashMap, fmt};
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::alpha1,
+    combinator::all_consuming,
+    multi::separated_list1,
+    sequence::{delimited, preceded},
+    IResult,
+};
+use pathfinding::directed::dijkstra::dijkstra_all;
+
+use crate::{error::parse_lines, verbose, Answer, SolveResult, Solver};
 
 pub struct Day;
 
+solution!(Day);
+
 impl Solver for Day {
-    fn part_1(&self, input: &str) -> String {
-        todo!()
+    type Input = Network;
+
+    fn parse(&self, input: &str) -> SolveResult<Self::Input> {
+        let valves = parse_lines(input, |l| Ok(all_consuming(Valve::parse)(l)?.1))?;
+        Network::compress(&valves)
+    }
+
+    fn part_1(&self, network: &Self::Input) -> SolveResult<Answer> {
+        let plan = network.plan(30, Actors::One)?;
+        print_plan(&plan);
+        Ok(released(&plan).into())
+    }
+
+    fn part_2(&self, network: &Self::Input) -> SolveResult<Answer> {
+        let plan = network.plan(26, Actors::Two)?;
+        print_plan(&plan);
+        Ok(released(&plan).into())
+    }
+}
+
+/// A line of the input, the tunnels are the names of the neighbouring valves
+struct Valve<'a> {
+    name: &'a str,
+    flow: u32,
+    tunnels: Vec<&'a str>,
+}
+
+impl<'a> Valve<'a> {
+    fn parse(i: &'a str) -> IResult<&'a str, Self> {
+        let (i, name) = preceded(tag("Valve "), alpha1)(i)?;
+        let (i, flow) = delimited(
+            tag(" has flow rate="),
+            nom::character::complete::u32,
+            alt((
+                tag("; tunnels lead to valves "),
+                tag("; tunnel leads to valve "),
+            )),
+        )(i)?;
+        let (i, tunnels) = separated_list1(tag(", "), alpha1)(i)?;
+        Ok((
+            i,
+            Self {
+                name,
+                flow,
+                tunnels,
+            },
+        ))
+    }
+}
+
+/// How many walkers open valves side by side
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Actors {
+    One,
+    Two,
+}
+
+const START: &str = "AA";
+/// Sets of valves index a table, so keep those to a few MB
+const MAX_VALVES: usize = 20;
+
+/// The valves worth opening and the start, with the walking time between any two.
+///
+/// Valves without flow are only ever walked through, so they are left out and the
+/// tunnels through them become longer walks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Network {
+    names: Vec<String>,
+    flows: Vec<u32>,
+    /// `dist[a][b]` is the number of minutes from valve `a` to valve `b`
+    dist: Vec<Vec<u32>>,
+    start: usize,
+}
+
+impl Network {
+    fn compress(valves: &[Valve]) -> SolveResult<Self> {
+        let index: HashMap<&str, usize> = valves
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (v.name, i))
+            .collect();
+        let tunnels = valves
+            .iter()
+            .map(|v| {
+                v.tunnels
+                    .iter()
+                    .map(|t| {
+                        index
+                            .get(t)
+                            .copied()
+                            .ok_or_else(|| format!("valve {} leads to unknown valve {t}", v.name))
+                    })
+                    .collect::<Result<Vec<usize>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let kept: Vec<usize> = (0..valves.len())
+            .filter(|&i| valves[i].flow > 0 || valves[i].name == START)
+            .collect();
+        let start = kept
+            .iter()
+            .position(|&i| valves[i].name == START)
+            .ok_or(format!("there is no valve {START}"))?;
+        if kept.len() > MAX_VALVES {
+            return Err(format!("{} valves with flow are too many to search", kept.len()).into());
+        }
+
+        let mut dist = vec![vec![0; kept.len()]; kept.len()];
+        for (a, &from) in kept.iter().enumerate() {
+            let reached = dijkstra_all(&from, |&v| tunnels[v].iter().map(|&t| (t, 1)));
+            for (b, to) in kept.iter().enumerate().filter(|&(b, _)| b != a) {
+                dist[a][b] = reached.get(to).map(|&(_, d)| d).ok_or_else(|| {
+                    format!(
+                        "valve {} can't be reached from {}",
+                        valves[*to].name, valves[from].name
+                    )
+                })?;
+            }
+        }
+
+        Ok(Self {
+            names: kept.iter().map(|&i| valves[i].name.to_string()).collect(),
+            flows: kept.iter().map(|&i| valves[i].flow).collect(),
+            dist,
+            start,
+        })
+    }
+
+    /// The valves the `actors` open for the most pressure released in `minutes`, one
+    /// schedule per actor
+    pub fn plan(&self, minutes: u32, actors: Actors) -> SolveResult<Vec<Schedule>> {
+        let best = self.best_per_set(minutes);
+        let sets = match actors {
+            Actors::One => {
+                let (set, _) = best
+                    .iter()
+                    .enumerate()
+                    .max_by_key(|&(_, &r)| r)
+                    .ok_or("there are no valves to open")?;
+                vec![set]
+            }
+            Actors::Two => {
+                let (a, b) = best_disjoint_pair(&best);
+                vec![a, b]
+            }
+        };
+        Ok(sets
+            .into_iter()
+            .map(|set| self.schedule(minutes, set, best[set]))
+            .collect())
+    }
+
+    /// The most pressure a single actor releases in `minutes` by opening exactly the
+    /// valves of a set, indexed by the set as a bitmask of valve indices. Sets that
+    /// can't be opened in time release nothing.
+    fn best_per_set(&self, minutes: u32) -> Vec<u32> {
+        let mut best = vec![0; 1 << self.flows.len()];
+        self.explore(self.start, minutes, 0, 0, &mut best);
+        best
+    }
+
+    fn explore(&self, at: usize, left: u32, open: usize, released: u32, best: &mut [u32]) {
+        best[open] = best[open].max(released);
+        for (next, left) in self.reachable(at, left, !open) {
+            let released = released + self.flows[next] * left;
+            self.explore(next, left, open | 1 << next, released, best);
+        }
+    }
+
+    /// The valves in `set` that can be walked to and opened from `at` with time left
+    /// to spare, with the minutes left once they are open
+    fn reachable(
+        &self,
+        at: usize,
+        left: u32,
+        set: usize,
+    ) -> impl Iterator<Item = (usize, u32)> + '_ {
+        (0..self.flows.len())
+            .filter(move |&v| set & 1 << v != 0 && self.flows[v] > 0)
+            .filter_map(move |v| Some((v, left.checked_sub(self.dist[at][v] + 1)?)))
+            .filter(|&(_, left)| left > 0)
     }
 
-    fn part_2(&self, input: &str) -> String {
-        todo!()
+    /// The order in which to open exactly the valves in `set` so that they release
+    /// `released`, which [`Network::best_per_set`] found to be possible.
+    ///
+    /// The search gives up on a branch as soon as walking straight to every valve left
+    /// can't release enough anymore, that cuts it down to a handful of orders.
+    fn schedule(&self, minutes: u32, set: usize, released: u32) -> Schedule {
+        fn find(
+            n: &Network,
+            at: usize,
+            left: u32,
+            todo: usize,
+            need: u32,
+        ) -> Option<Vec<(usize, u32)>> {
+            if todo == 0 {
+                return (need == 0).then(Vec::new);
+            }
+            let reachable = || n.reachable(at, left, todo);
+            if reachable().map(|(v, l)| n.flows[v] * l).sum::<u32>() < need {
+                return None;
+            }
+            reachable()
+                .filter(|&(v, l)| n.flows[v] * l <= need)
+                .find_map(|(v, l)| {
+                    let mut rest = find(n, v, l, todo & !(1 << v), need - n.flows[v] * l)?;
+                    rest.insert(0, (v, l));
+                    Some(rest)
+                })
+        }
+
+        let order = find(self, self.start, minutes, set, released)
+            .expect("the release of the set was reached by some order");
+        Schedule {
+            openings: order
+                .into_iter()
+                .map(|(v, left)| Opening {
+                    minute: minutes - left,
+                    valve: self.names[v].clone(),
+                    released: self.flows[v] * left,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The two disjoint sets that release the most together
+fn best_disjoint_pair(best: &[u32]) -> (usize, usize) {
+    let mut sets: Vec<(usize, u32)> = best
+        .iter()
+        .enumerate()
+        .filter(|&(s, &r)| r > 0 || s == 0)
+        .map(|(s, &r)| (s, r))
+        .collect();
+    sets.sort_unstable_by_key(|&(_, r)| Reverse(r));
+
+    let mut most = (0, 0, 0);
+    for (i, &(a, ra)) in sets.iter().enumerate() {
+        // the partners left release at most as much as `a` does
+        if ra * 2 <= most.2 {
+            break;
+        }
+        if let Some(&(b, rb)) = sets[i..].iter().find(|&&(b, _)| a & b == 0) {
+            if ra + rb > most.2 {
+                most = (a, b, ra + rb);
+            }
+        }
+    }
+    (most.0, most.1)
+}
+
+fn released(schedules: &[Schedule]) -> u32 {
+    schedules.iter().map(Schedule::released).sum()
+}
+
+fn print_plan(schedules: &[Schedule]) {
+    if verbose() {
+        for (i, s) in schedules.iter().enumerate() {
+            eprintln!("actor {}:\n{s}", i + 1);
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Opening {
+    /// The minute at whose end the valve is open
+    pub minute: u32,
+    pub valve: String,
+    /// Everything it releases until the time is up
+    pub released: u32,
+}
+
+/// The valves one actor opens, in order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Schedule {
+    pub openings: Vec<Opening>,
+}
+
+impl Schedule {
+    pub fn released(&self) -> u32 {
+        self.openings.iter().map(|o| o.released).sum()
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for o in &self.openings {
+            writeln!(
+                f,
+                "minute {:2}: open {}, releasing {}",
+                o.minute, o.valve, o.released
+            )?;
+        }
+        write!(f, "total: {}", self.released())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = "";
+    const INPUT: &str = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II
+";
+
+    fn valves(schedule: &Schedule) -> Vec<(u32, &str)> {
+        schedule
+            .openings
+            .iter()
+            .map(|o| (o.minute, o.valve.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn compress() {
+        let n = Day.parse(INPUT).unwrap();
+        assert_eq!(n.names, ["AA", "BB", "CC", "DD", "EE", "HH", "JJ"]);
+        assert_eq!(n.start, 0);
+        // AA -> DD -> EE -> FF -> GG -> HH
+        assert_eq!(n.dist[0][5], 5);
+        assert_eq!(n.dist[6][5], 7);
+    }
+
+    #[test]
+    fn part_1() {
+        let n = Day.parse(INPUT).unwrap();
+        let plan = n.plan(30, Actors::One).unwrap();
+        assert_eq!(
+            valves(&plan[0]),
+            [
+                (2, "DD"),
+                (5, "BB"),
+                (9, "JJ"),
+                (17, "HH"),
+                (21, "EE"),
+                (24, "CC")
+            ]
+        );
+        assert_eq!(Day.part_1(&n), Ok(1651.into()));
+    }
+
+    #[test]
+    fn part_2() {
+        let n = Day.parse(INPUT).unwrap();
+        let plan = n.plan(26, Actors::Two).unwrap();
+        let mut valves: Vec<_> = plan.iter().map(valves).collect();
+        valves.sort();
+        assert_eq!(
+            valves,
+            [
+                vec![(2, "DD"), (7, "HH"), (11, "EE")],
+                vec![(3, "JJ"), (7, "BB"), (9, "CC")]
+            ]
+        );
+        assert_eq!(Day.part_2(&n), Ok(1707.into()));
+    }
+
+    #[test]
+    fn bad_network() {
+        let e = Day.parse("Valve BB has flow rate=1; tunnel leads to valve AA");
+        assert_eq!(
+            e.unwrap_err().to_string(),
+            "valve BB leads to unknown valve AA"
+        );
+        let e = Day.parse("Valve BB has flow rate=1; tunnel leads to valve BB");
+        assert_eq!(e.unwrap_err().to_string(), "there is no valve AA");
+    }
+
+    #[test]
+    fn display() {
+        let n = Day.parse(INPUT).unwrap();
+        let schedule = n.schedule(30, 1 << 3 | 1 << 1, 885);
+        assert_eq!(
+            schedule.to_string(),
+            "minute  2: open DD, releasing 560\n\
+             minute  5: open BB, releasing 325\n\
+             total: 885"
+        );
+    }
 }
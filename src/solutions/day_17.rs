@@ -1,19 +1,210 @@
-use crate::Solver;
+use std::{collections::HashMap, fmt};
+
+use crate::{Answer, SolveResult, Solver};
 
 pub struct Day;
 
+solution!(Day);
+
 impl Solver for Day {
-    fn part_1(&self, input: &str) -> String {
-        todo!()
+    type Input = Vec<Jet>;
+
+    fn parse(&self, input: &str) -> SolveResult<Self::Input> {
+        let jets = input
+            .trim_end()
+            .chars()
+            .map(|c| match c {
+                '<' => Ok(Jet::Left),
+                '>' => Ok(Jet::Right),
+                c => Err(format!("{c:?} is not a jet")),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if jets.is_empty() {
+            return Err("there are no jets".into());
+        }
+        Ok(jets)
+    }
+
+    fn part_1(&self, jets: &Self::Input) -> SolveResult<Answer> {
+        Ok(height_after(jets, 2022).into())
+    }
+
+    fn part_2(&self, jets: &Self::Input) -> SolveResult<Answer> {
+        Ok(height_after(jets, 1_000_000_000_000).into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Jet {
+    Left,
+    Right,
+}
+
+const WIDTH: usize = 7;
+
+/// The rocks in the order they fall, one byte per row from the bottom up. Bit `x` is
+/// column `x` counted from the left wall.
+const ROCKS: [&[u8]; 5] = [
+    &[0b1111],
+    &[0b010, 0b111, 0b010],
+    &[0b111, 0b100, 0b100],
+    &[0b1, 0b1, 0b1, 0b1],
+    &[0b11, 0b11],
+];
+
+/// The rows of the chamber that hold rock, bit-packed like [`ROCKS`]
+#[derive(Debug, Clone, Default)]
+pub struct Chamber {
+    rows: Vec<u8>,
+    /// How many rocks have come to rest
+    rocks: usize,
+    /// The next jet to push a rock
+    jet: usize,
+}
+
+impl Chamber {
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Lets the next rock fall until it comes to rest
+    pub fn drop_rock(&mut self, jets: &[Jet]) {
+        // two units from the left wall, three rows above the highest rock
+        let mut rock: Vec<u8> = ROCKS[self.rocks % ROCKS.len()]
+            .iter()
+            .map(|r| r << 2)
+            .collect();
+        let mut y = self.height() + 3;
+
+        loop {
+            let pushed: Vec<u8> = match jets[self.jet] {
+                Jet::Left if rock.iter().all(|r| r & 1 == 0) => {
+                    rock.iter().map(|r| r >> 1).collect()
+                }
+                Jet::Right if rock.iter().all(|r| r & 1 << (WIDTH - 1) == 0) => {
+                    rock.iter().map(|r| r << 1).collect()
+                }
+                _ => rock.clone(),
+            };
+            self.jet = (self.jet + 1) % jets.len();
+            if self.fits(&pushed, y) {
+                rock = pushed;
+            }
+
+            match y.checked_sub(1) {
+                Some(below) if self.fits(&rock, below) => y = below,
+                _ => break,
+            }
+        }
+
+        for (i, r) in rock.into_iter().enumerate() {
+            match self.rows.get_mut(y + i) {
+                Some(row) => *row |= r,
+                None => self.rows.push(r),
+            }
+        }
+        self.rocks += 1;
+    }
+
+    fn fits(&self, rock: &[u8], y: usize) -> bool {
+        rock.iter()
+            .zip(self.rows.iter().skip(y))
+            .all(|(r, row)| r & row == 0)
+    }
+
+    /// How far below the top the highest rock of every column is. Together with the
+    /// next rock and jet this decides everything that happens from here on, as long as
+    /// nothing falls past the deepest of them (which doesn't happen in practice).
+    fn surface(&self) -> [usize; WIDTH] {
+        let mut surface = [self.height(); WIDTH];
+        for (x, depth) in surface.iter_mut().enumerate() {
+            if let Some(d) = self.rows.iter().rev().position(|row| row & 1 << x != 0) {
+                *depth = d;
+            }
+        }
+        surface
+    }
+}
+
+/// Draws the chamber like the puzzle does, the top row first
+impl fmt::Display for Chamber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows.iter().rev() {
+            let cells: String = (0..WIDTH)
+                .map(|x| if row & 1 << x != 0 { '#' } else { '.' })
+                .collect();
+            writeln!(f, "|{cells}|")?;
+        }
+        write!(f, "+{}+", "-".repeat(WIDTH))
     }
+}
+
+/// The height of the tower once `rocks` rocks have come to rest.
+///
+/// The falling soon repeats itself, once a state is seen a second time the remaining
+/// whole cycles are skipped and only the rest is simulated.
+fn height_after(jets: &[Jet], rocks: usize) -> usize {
+    let mut chamber = Chamber::default();
+    let mut seen = HashMap::new();
+    let mut skipped = 0;
 
-    fn part_2(&self, input: &str) -> String {
-        todo!()
+    while chamber.rocks < rocks {
+        chamber.drop_rock(jets);
+        if skipped > 0 {
+            continue;
+        }
+        let state = (chamber.rocks % ROCKS.len(), chamber.jet, chamber.surface());
+        if let Some((rocks_before, height_before)) =
+            seen.insert(state, (chamber.rocks, chamber.height()))
+        {
+            let cycle = chamber.rocks - rocks_before;
+            let cycles = (rocks - chamber.rocks) / cycle;
+            skipped = cycles * (chamber.height() - height_before);
+            chamber.rocks += cycles * cycle;
+        }
     }
+    chamber.height() + skipped
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = "";
+    const INPUT: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>\n";
+
+    #[test]
+    fn render() {
+        let jets = Day.parse(INPUT).unwrap();
+        let mut chamber = Chamber::default();
+        chamber.drop_rock(&jets);
+        assert_eq!(chamber.to_string(), "|..####.|\n+-------+");
+        chamber.drop_rock(&jets);
+        chamber.drop_rock(&jets);
+        assert_eq!(
+            chamber.to_string(),
+            "|..#....|\n\
+             |..#....|\n\
+             |####...|\n\
+             |..###..|\n\
+             |...#...|\n\
+             |..####.|\n\
+             +-------+"
+        );
+    }
+
+    #[test]
+    fn cycles() {
+        let jets = Day.parse(INPUT).unwrap();
+        let mut chamber = Chamber::default();
+        for _ in 0..5000 {
+            chamber.drop_rock(&jets);
+        }
+        assert_eq!(height_after(&jets, 5000), chamber.height());
+    }
+
+    #[test]
+    fn parts() {
+        let jets = Day.parse(INPUT).unwrap();
+        assert_eq!(Day.part_1(&jets), Ok(3068.into()));
+        assert_eq!(Day.part_2(&jets), Ok(1514285714288usize.into()));
+    }
 }
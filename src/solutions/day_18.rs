@@ -1,19 +1,150 @@
-use crate::Solver;
+use std::collections::HashSet;
+
+use itertools::Itertools;
+use pathfinding::directed::bfs::bfs_reach;
+
+use crate::{error::parse_lines, geom::Vec3, Answer, SolveResult, Solver};
 
 pub struct Day;
 
+solution!(Day);
+
 impl Solver for Day {
-    fn part_1(&self, input: &str) -> String {
-        todo!()
+    type Input = Droplet;
+
+    fn parse(&self, input: &str) -> SolveResult<Self::Input> {
+        let cubes = parse_lines(input, |l| {
+            let (x, y, z) = l
+                .split(',')
+                .map(|c| c.parse::<i32>().map_err(|e| format!("{c:?}: {e}")))
+                .collect_tuple()
+                .ok_or("expected x,y,z")?;
+            Ok(Vec3::new(x?, y?, z?))
+        })?;
+        Ok(Droplet {
+            cubes: cubes.into_iter().collect(),
+        })
     }
 
-    fn part_2(&self, input: &str) -> String {
-        todo!()
+    fn part_1(&self, droplet: &Self::Input) -> SolveResult<Answer> {
+        Ok(droplet.surface_area().into())
+    }
+
+    fn part_2(&self, droplet: &Self::Input) -> SolveResult<Answer> {
+        Ok(droplet.exterior_surface_area().into())
+    }
+}
+
+/// A unit cube of the scan, by its corner
+pub type Point3 = Vec3<i32>;
+
+/// The six points sharing a face with `p`
+fn neighbours(p: Point3) -> impl Iterator<Item = Point3> {
+    Point3::UNITS.into_iter().map(move |d| p + d)
+}
+
+/// An axis-aligned box, both corners included
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Cuboid {
+    min: Point3,
+    max: Point3,
+}
+
+impl Cuboid {
+    fn contains(&self, p: Point3) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x)
+            && (self.min.y..=self.max.y).contains(&p.y)
+            && (self.min.z..=self.max.z).contains(&p.z)
+    }
+
+    /// One more layer all around
+    fn grow(self) -> Self {
+        let one = Vec3::new(1, 1, 1);
+        Self {
+            min: self.min - one,
+            max: self.max + one,
+        }
+    }
+}
+
+/// Every point reachable from `start` through face neighbours that are `open`
+fn flood_fill(start: Point3, open: impl Fn(Point3) -> bool) -> HashSet<Point3> {
+    bfs_reach(start, |&p| neighbours(p).filter(|&n| open(n)).collect_vec()).collect()
+}
+
+/// The unit cubes of lava
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Droplet {
+    cubes: HashSet<Point3>,
+}
+
+impl Droplet {
+    fn bounds(&self) -> Option<Cuboid> {
+        let (min_x, max_x) = self.cubes.iter().map(|p| p.x).minmax().into_option()?;
+        let (min_y, max_y) = self.cubes.iter().map(|p| p.y).minmax().into_option()?;
+        let (min_z, max_z) = self.cubes.iter().map(|p| p.z).minmax().into_option()?;
+        Some(Cuboid {
+            min: Vec3::new(min_x, min_y, min_z),
+            max: Vec3::new(max_x, max_y, max_z),
+        })
+    }
+
+    /// Faces of cubes that don't touch another cube
+    fn surface_area(&self) -> usize {
+        self.faces_towards(|p| !self.cubes.contains(&p))
+    }
+
+    /// Faces that can be reached from outside, air pockets inside don't count
+    fn exterior_surface_area(&self) -> usize {
+        let Some(bounds) = self.bounds() else {
+            return 0;
+        };
+        // with a layer of air around the droplet, all of the outside is connected
+        let bounds = bounds.grow();
+        let outside = flood_fill(bounds.min, |p| {
+            bounds.contains(p) && !self.cubes.contains(&p)
+        });
+        self.faces_towards(|p| outside.contains(&p))
+    }
+
+    fn faces_towards(&self, open: impl Fn(Point3) -> bool) -> usize {
+        self.cubes
+            .iter()
+            .flat_map(|&c| neighbours(c))
+            .filter(|&n| open(n))
+            .count()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = "";
+    const INPUT: &str = "2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5
+";
+
+    #[test]
+    fn two_cubes() {
+        let droplet = Day.parse("1,1,1\n2,1,1\n").unwrap();
+        assert_eq!(droplet.surface_area(), 10);
+        assert_eq!(droplet.exterior_surface_area(), 10);
+    }
+
+    #[test]
+    fn parts() {
+        let droplet = Day.parse(INPUT).unwrap();
+        assert_eq!(Day.part_1(&droplet), Ok(64.into()));
+        assert_eq!(Day.part_2(&droplet), Ok(58.into()));
+    }
 }
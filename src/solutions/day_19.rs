@@ -1,19 +1,255 @@
-use crate::Solver;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{multispace0, multispace1},
+    combinator::all_consuming,
+    multi::separated_list1,
+    sequence::{delimited, preceded, terminated, tuple},
+    IResult,
+};
+
+use crate::{Answer, SolveResult, Solver};
 
 pub struct Day;
 
+solution!(Day);
+
 impl Solver for Day {
-    fn part_1(&self, input: &str) -> String {
-        todo!()
+    type Input = Vec<Blueprint>;
+
+    fn parse(&self, input: &str) -> SolveResult<Self::Input> {
+        // the example wraps every blueprint over several lines, the real input doesn't
+        let (_, blueprints) = all_consuming(delimited(
+            multispace0,
+            separated_list1(multispace1, Blueprint::parse),
+            multispace0,
+        ))(input)?;
+        Ok(blueprints)
+    }
+
+    fn part_1(&self, blueprints: &Self::Input) -> SolveResult<Answer> {
+        let geodes = max_geodes_in_parallel(blueprints, 24);
+        let quality: u32 = blueprints.iter().zip(geodes).map(|(b, g)| b.id * g).sum();
+        Ok(quality.into())
+    }
+
+    fn part_2(&self, blueprints: &Self::Input) -> SolveResult<Answer> {
+        let first = &blueprints[..blueprints.len().min(3)];
+        let geodes = max_geodes_in_parallel(first, 32);
+        Ok(geodes.into_iter().product::<u32>().into())
+    }
+}
+
+const ORE: usize = 0;
+const CLAY: usize = 1;
+const OBSIDIAN: usize = 2;
+const GEODE: usize = 3;
+
+/// Amounts of ore, clay, obsidian and geodes, in that order
+type Resources = [u32; 4];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blueprint {
+    id: u32,
+    /// `costs[robot]` is what it takes to build a robot collecting that resource
+    costs: [Resources; 4],
+}
+
+/// One sentence of a blueprint, `Each <kind> robot costs <costs>.`
+fn robot<'a, O>(
+    name: &'static str,
+    costs: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, O> {
+    preceded(multispace0, delimited(tag(name), costs, tag(".")))
+}
+
+impl Blueprint {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        let number = nom::character::complete::u32;
+
+        let (i, id) = delimited(tag("Blueprint "), number, tag(":"))(i)?;
+        let (i, ore) = robot("Each ore robot costs ", terminated(number, tag(" ore")))(i)?;
+        let (i, clay) = robot("Each clay robot costs ", terminated(number, tag(" ore")))(i)?;
+        let (i, (obsidian_ore, obsidian_clay)) = robot(
+            "Each obsidian robot costs ",
+            tuple((
+                terminated(number, tag(" ore and ")),
+                terminated(number, tag(" clay")),
+            )),
+        )(i)?;
+        let (i, (geode_ore, geode_obsidian)) = robot(
+            "Each geode robot costs ",
+            tuple((
+                terminated(number, tag(" ore and ")),
+                terminated(number, tag(" obsidian")),
+            )),
+        )(i)?;
+
+        Ok((
+            i,
+            Self {
+                id,
+                costs: [
+                    [ore, 0, 0, 0],
+                    [clay, 0, 0, 0],
+                    [obsidian_ore, obsidian_clay, 0, 0],
+                    [geode_ore, 0, geode_obsidian, 0],
+                ],
+            },
+        ))
+    }
+
+    /// More robots of a kind than the most any robot costs of it is no use, only one
+    /// robot can be built per minute. Geode robots are always useful.
+    fn max_robots(&self) -> [u32; 4] {
+        let mut max = [u32::MAX; 4];
+        for resource in [ORE, CLAY, OBSIDIAN] {
+            max[resource] = self.costs.iter().map(|c| c[resource]).max().unwrap_or(0);
+        }
+        max
+    }
+
+    /// The most geodes that can be opened in `minutes`
+    pub fn max_geodes(&self, minutes: u32) -> u32 {
+        let mut search = Search {
+            blueprint: self,
+            max_robots: self.max_robots(),
+            best: 0,
+        };
+        search.run(State {
+            left: minutes,
+            robots: [1, 0, 0, 0],
+            stock: [0; 4],
+        });
+        search.best
     }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct State {
+    left: u32,
+    robots: Resources,
+    stock: Resources,
+}
+
+impl State {
+    /// The geodes there will be if nothing else is built
+    fn geodes(&self) -> u32 {
+        self.stock[GEODE] + self.robots[GEODE] * self.left
+    }
+
+    /// More than can possibly be reached: a new geode robot every minute from now on
+    fn upper_bound(&self) -> u32 {
+        self.geodes() + self.left * self.left.saturating_sub(1) / 2
+    }
+
+    /// Waits until the robot can be afforded and builds it, if that happens in time to
+    /// still collect something with it
+    fn build(&self, robot: usize, cost: &Resources) -> Option<Self> {
+        let mut wait = 0;
+        for (resource, &needed) in cost.iter().enumerate() {
+            let missing = needed.saturating_sub(self.stock[resource]);
+            if missing > 0 {
+                let rate = self.robots[resource];
+                if rate == 0 {
+                    return None;
+                }
+                wait = wait.max(missing.div_ceil(rate));
+            }
+        }
+
+        // the robot is ready at the end of the minute after the wait
+        let passed = wait + 1;
+        if passed >= self.left {
+            return None;
+        }
+        let mut next = *self;
+        next.left -= passed;
+        for ((stock, rate), cost) in next.stock.iter_mut().zip(self.robots).zip(cost) {
+            *stock = *stock + rate * passed - cost;
+        }
+        next.robots[robot] += 1;
+        Some(next)
+    }
+}
 
-    fn part_2(&self, input: &str) -> String {
-        todo!()
+/// Depth first over which robot to build next, jumping straight to the minute it can
+/// be afforded instead of going minute by minute
+struct Search<'a> {
+    blueprint: &'a Blueprint,
+    max_robots: [u32; 4],
+    best: u32,
+}
+
+impl Search<'_> {
+    fn run(&mut self, state: State) {
+        self.best = self.best.max(state.geodes());
+        if state.upper_bound() <= self.best {
+            return;
+        }
+        // the most promising robots first, that raises `best` early
+        for robot in [GEODE, OBSIDIAN, CLAY, ORE] {
+            if state.robots[robot] >= self.max_robots[robot] {
+                continue;
+            }
+            if let Some(next) = state.build(robot, &self.blueprint.costs[robot]) {
+                self.run(next);
+            }
+        }
     }
 }
 
+/// [`Blueprint::max_geodes`] of every blueprint, each on its own thread
+fn max_geodes_in_parallel(blueprints: &[Blueprint], minutes: u32) -> Vec<u32> {
+    std::thread::scope(|s| {
+        let handles: Vec<_> = blueprints
+            .iter()
+            .map(|b| s.spawn(move || b.max_geodes(minutes)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|h| h.join().expect("the search doesn't panic"))
+            .collect()
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = "";
+    const INPUT: &str = "Blueprint 1:
+  Each ore robot costs 4 ore.
+  Each clay robot costs 2 ore.
+  Each obsidian robot costs 3 ore and 14 clay.
+  Each geode robot costs 2 ore and 7 obsidian.
+
+Blueprint 2:
+  Each ore robot costs 2 ore.
+  Each clay robot costs 3 ore.
+  Each obsidian robot costs 3 ore and 8 clay.
+  Each geode robot costs 3 ore and 12 obsidian.
+";
+
+    #[test]
+    fn parse() {
+        let blueprints = Day.parse(INPUT).unwrap();
+        let one_line = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. \
+                        Each obsidian robot costs 3 ore and 14 clay. \
+                        Each geode robot costs 2 ore and 7 obsidian.\n";
+        assert_eq!(Day.parse(one_line).unwrap()[0], blueprints[0]);
+        assert_eq!(blueprints[1].costs[GEODE], [3, 0, 12, 0]);
+        assert_eq!(blueprints[0].max_robots(), [4, 14, 7, u32::MAX]);
+    }
+
+    #[test]
+    fn part_1() {
+        let blueprints = Day.parse(INPUT).unwrap();
+        assert_eq!(max_geodes_in_parallel(&blueprints, 24), [9, 12]);
+        assert_eq!(Day.part_1(&blueprints), Ok(33.into()));
+    }
+
+    #[test]
+    fn part_2() {
+        let blueprints = Day.parse(INPUT).unwrap();
+        assert_eq!(blueprints[0].max_geodes(32), 56);
+        assert_eq!(blueprints[1].max_geodes(32), 62);
+    }
 }
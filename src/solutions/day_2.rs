@@ -1,19 +1,34 @@
-use crate::Solver;
+use crate::{error::parse_lines, Answer, SolveResult, Solver};
 
 pub struct Day;
 
+solution!(Day);
+
 impl Solver for Day {
-    fn part_1(&self, input: &str) -> String {
-        part1::solve(input).to_string()
+    /// Both columns of every round as 0, 1 or 2, what they mean depends on the part
+    type Input = Vec<(u8, u8)>;
+
+    fn parse(&self, input: &str) -> SolveResult<Self::Input> {
+        parse_lines(input, parse_line)
     }
 
-    fn part_2(&self, input: &str) -> String {
-        part2::solve(input).to_string()
+    fn part_1(&self, rounds: &Self::Input) -> SolveResult<Answer> {
+        Ok(part1::solve(rounds).into())
+    }
+
+    fn part_2(&self, rounds: &Self::Input) -> SolveResult<Answer> {
+        Ok(part2::solve(rounds).into())
     }
 }
 
-mod part1 {
+fn parse_line(l: &str) -> SolveResult<(u8, u8)> {
+    match l.as_bytes() {
+        &[them @ b'A'..=b'C', b' ', col @ b'X'..=b'Z'] => Ok((them - b'A', col - b'X')),
+        _ => Err(format!("expected something like \"A Y\", got {l:?}").into()),
+    }
+}
 
+mod part1 {
     #[derive(Clone, Copy, Debug)]
     enum Rps {
         Rock,
@@ -53,15 +68,12 @@ mod part1 {
         }
     }
 
-    impl TryFrom<char> for Rps {
-        type Error = &'static str;
-
-        fn try_from(value: char) -> Result<Self, Self::Error> {
-            match value.to_ascii_lowercase() {
-                'a' | 'x' => Ok(Rps::Rock),
-                'b' | 'y' => Ok(Rps::Paper),
-                'c' | 'z' => Ok(Rps::Scissors),
-                _ => Err("not a valid Rps"),
+    impl From<u8> for Rps {
+        fn from(value: u8) -> Self {
+            match value {
+                0 => Rps::Rock,
+                1 => Rps::Paper,
+                _ => Rps::Scissors,
             }
         }
     }
@@ -83,46 +95,32 @@ mod part1 {
         }
     }
 
-    fn parse_line(line: &str) -> Option<(Rps, Rps)> {
-        let mut it = line.chars();
-        let them = it.next().unwrap().try_into().unwrap();
-        let us = it.skip(1).next().unwrap().try_into().unwrap();
-        Some((us, them))
-    }
-
-    pub(crate) fn solve(input: &str) -> i64 {
+    pub(crate) fn solve(rounds: &[(u8, u8)]) -> i64 {
         let mut sum: i64 = 0;
-        let input = input.lines();
 
-        for (us, them) in input.filter_map(|l| parse_line(l)) {
+        for &(them, us) in rounds {
+            let (us, them) = (Rps::from(us), Rps::from(them));
             let val1: i64 = us.into();
             let val2: i64 = us.battle(them).into();
             sum += val1 + val2;
         }
-        return sum;
+        sum
     }
 }
 
 mod part2 {
-
-    pub(crate) fn solve(input: &str) -> i64 {
-        let lines = input.lines();
+    pub(crate) fn solve(rounds: &[(u8, u8)]) -> i64 {
         let mut sum = 0;
-        for l in lines {
-            let b = l.as_bytes();
-            let them = b[0];
-            let res = (b[2] - b'X') * 3;
-
-            let them = (them - b'A') as i64;
+        for &(them, res) in rounds {
+            let (them, res) = (them as i64, res * 3);
 
             let us = match res {
                 0 => (them - 1).rem_euclid(3),
                 3 => them,
-                6 => (them + 1).rem_euclid(3),
-                _ => panic!(),
+                _ => (them + 1).rem_euclid(3),
             };
 
-            sum += us as i64 + 1 + res as i64;
+            sum += us + 1 + res as i64;
         }
         sum
     }
@@ -131,10 +129,24 @@ mod part2 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::SolveError;
     const TEST: &str = "A Y\nB X\nC Z";
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1::solve(TEST), 15);
+        assert_eq!(part1::solve(&Day.parse(TEST).unwrap()), 15);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2::solve(&Day.parse(TEST).unwrap()), 12);
+    }
+
+    #[test]
+    fn bad_line() {
+        assert_eq!(
+            Day.parse("A Y\nB W").unwrap_err(),
+            SolveError::at(2, "expected something like \"A Y\", got \"B W\"")
+        );
     }
 }
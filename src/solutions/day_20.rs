@@ -1,19 +1,185 @@
-use crate::Solver;
+use crate::{error::parse_lines, Answer, SolveResult, Solver};
 
 pub struct Day;
 
+solution!(Day);
+
+const DECRYPTION_KEY: i64 = 811_589_153;
+
 impl Solver for Day {
-    fn part_1(&self, input: &str) -> String {
-        todo!()
+    type Input = Vec<i64>;
+
+    fn parse(&self, input: &str) -> SolveResult<Self::Input> {
+        parse_lines(input, |l| {
+            l.parse().map_err(|e| format!("{l:?}: {e}").into())
+        })
+    }
+
+    fn part_1(&self, numbers: &Self::Input) -> SolveResult<Answer> {
+        let mut mixer = Mixer::new(numbers.clone());
+        mixer.mix();
+        Ok(grove_coordinates(&mixer.order())?.into())
     }
 
-    fn part_2(&self, input: &str) -> String {
-        todo!()
+    fn part_2(&self, numbers: &Self::Input) -> SolveResult<Answer> {
+        let mut mixer = Mixer::new(numbers.iter().map(|n| n * DECRYPTION_KEY).collect());
+        for _ in 0..10 {
+            mixer.mix();
+        }
+        Ok(grove_coordinates(&mixer.order())?.into())
     }
 }
 
+/// A circular list that moves its numbers around by their value.
+///
+/// The numbers are kept as their original indices in blocks of about `√n`, so
+/// finding, removing and inserting one only walks over a block and the block lengths.
+/// Mixing is `O(n√n)` instead of the `O(n²)` of shifting a single `Vec` around.
+#[derive(Debug, Clone)]
+pub struct Mixer {
+    values: Vec<i64>,
+    blocks: Vec<Vec<usize>>,
+    /// The block each original index is in
+    block_of: Vec<usize>,
+    block_size: usize,
+}
+
+impl Mixer {
+    pub fn new(values: Vec<i64>) -> Self {
+        let block_size = (values.len() as f64).sqrt().ceil().max(1.0) as usize;
+        let mut mixer = Self {
+            blocks: vec![],
+            block_of: vec![0; values.len()],
+            values,
+            block_size,
+        };
+        mixer.rebalance((0..mixer.values.len()).collect());
+        mixer
+    }
+
+    /// Moves every number once, in their original order
+    pub fn mix(&mut self) {
+        for i in 0..self.values.len() {
+            self.move_number(i);
+        }
+    }
+
+    /// The numbers in their current order, starting anywhere in the circle
+    pub fn order(&self) -> Vec<i64> {
+        self.blocks
+            .iter()
+            .flatten()
+            .map(|&i| self.values[i])
+            .collect()
+    }
+
+    /// Moves the number first at `i` by its value, the list is circular and the
+    /// number itself doesn't count as a step so a move wraps every `len - 1`
+    fn move_number(&mut self, i: usize) {
+        let len = self.values.len();
+        if len < 2 {
+            return;
+        }
+
+        let block = self.block_of[i];
+        let offset = self.blocks[block].iter().position(|&j| j == i).unwrap();
+        let from = self.blocks[..block].iter().map(Vec::len).sum::<usize>() + offset;
+        self.blocks[block].remove(offset);
+
+        let mut to = (from as i64 + self.values[i]).rem_euclid(len as i64 - 1) as usize;
+        let mut block = 0;
+        while to > self.blocks[block].len() {
+            to -= self.blocks[block].len();
+            block += 1;
+        }
+        self.blocks[block].insert(to, i);
+        self.block_of[i] = block;
+
+        if self.blocks[block].len() > 2 * self.block_size {
+            let order = self.blocks.iter().flatten().copied().collect();
+            self.rebalance(order);
+        }
+    }
+
+    /// Splits `order` into blocks of the same size again
+    fn rebalance(&mut self, order: Vec<usize>) {
+        self.blocks = order
+            .chunks(self.block_size)
+            .map(<[usize]>::to_vec)
+            .collect();
+        for (b, block) in self.blocks.iter().enumerate() {
+            for &i in block {
+                self.block_of[i] = b;
+            }
+        }
+    }
+}
+
+/// The sum of the 1000th, 2000th and 3000th numbers after the 0
+fn grove_coordinates(order: &[i64]) -> SolveResult<i64> {
+    let zero = order.iter().position(|&n| n == 0).ok_or("there is no 0")?;
+    Ok([1000, 2000, 3000]
+        .iter()
+        .map(|offset| order[(zero + offset) % order.len()])
+        .sum())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = "";
+    const INPUT: &str = "1\n2\n-3\n3\n-2\n0\n4\n";
+
+    /// The `O(n²)` mixing, a single `Vec` of (original index, value)
+    fn naive_mix(numbers: &[i64], rounds: usize) -> Vec<i64> {
+        let mut list: Vec<(usize, i64)> = numbers.iter().copied().enumerate().collect();
+        for _ in 0..rounds {
+            for i in 0..numbers.len() {
+                let from = list.iter().position(|&(j, _)| j == i).unwrap();
+                let n = list.remove(from);
+                let to = (from as i64 + n.1).rem_euclid(numbers.len() as i64 - 1);
+                list.insert(to as usize, n);
+            }
+        }
+        list.into_iter().map(|(_, n)| n).collect()
+    }
+
+    /// Rotates the circle so it starts at `start`, which has to be unique, to compare
+    /// orders
+    fn starting_at(order: &[i64], start: i64) -> Vec<i64> {
+        assert_eq!(order.iter().filter(|&&n| n == start).count(), 1);
+        let i = order.iter().position(|&n| n == start).unwrap();
+        order[i..].iter().chain(&order[..i]).copied().collect()
+    }
+
+    #[test]
+    fn mix() {
+        let numbers = Day.parse(INPUT).unwrap();
+        let mut mixer = Mixer::new(numbers);
+        mixer.mix();
+        assert_eq!(starting_at(&mixer.order(), 0), [0, 3, -2, 1, 2, -3, 4]);
+    }
+
+    #[test]
+    fn like_naive() {
+        // many 0, 1 and -3, moves longer than the list and one 7 to line the circles up
+        let mut numbers: Vec<i64> = (0..400)
+            .map(|i| [0, 1, -3, 1, 0, 2500, -3, -1201][i % 8])
+            .collect();
+        numbers[123] = 7;
+        let mut mixer = Mixer::new(numbers.clone());
+        for _ in 0..3 {
+            mixer.mix();
+        }
+        assert_eq!(
+            starting_at(&mixer.order(), 7),
+            starting_at(&naive_mix(&numbers, 3), 7)
+        );
+    }
+
+    #[test]
+    fn parts() {
+        let numbers = Day.parse(INPUT).unwrap();
+        assert_eq!(Day.part_1(&numbers), Ok(3.into()));
+        assert_eq!(Day.part_2(&numbers), Ok(1623178306.into()));
+    }
 }
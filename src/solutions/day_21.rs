@@ -1,19 +1,329 @@
-use crate::Solver;
+use std::{collections::HashMap, fmt};
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alpha1, one_of},
+    combinator::{all_consuming, map},
+    sequence::{delimited, separated_pair, tuple},
+    IResult,
+};
+
+use crate::{error::parse_lines, Answer, SolveResult, Solver};
 
 pub struct Day;
 
+solution!(Day);
+
+const ROOT: &str = "root";
+const HUMAN: &str = "humn";
+
 impl Solver for Day {
-    fn part_1(&self, input: &str) -> String {
-        todo!()
+    type Input = Monkeys;
+
+    fn parse(&self, input: &str) -> SolveResult<Self::Input> {
+        let lines = parse_lines(input, |l| {
+            let (_, (name, job)) =
+                all_consuming(separated_pair(alpha1, tag(": "), RawJob::parse))(l)?;
+            Ok((name, job))
+        })?;
+        Monkeys::new(&lines)
+    }
+
+    fn part_1(&self, monkeys: &Self::Input) -> SolveResult<Answer> {
+        Ok(monkeys.eval(monkeys.id(ROOT)?)?.into())
+    }
+
+    fn part_2(&self, monkeys: &Self::Input) -> SolveResult<Answer> {
+        Ok(monkeys.equation()?.solve()?.into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operation {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl Operation {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        map(one_of("+-*/"), |op| match op {
+            '+' => Self::Add,
+            '-' => Self::Sub,
+            '*' => Self::Mul,
+            '/' => Self::Div,
+            _ => unreachable!(),
+        })(i)
+    }
+
+    fn eval(self, a: i64, b: i64) -> SolveResult<i64> {
+        match self {
+            Operation::Add => Ok(a + b),
+            Operation::Sub => Ok(a - b),
+            Operation::Mul => Ok(a * b),
+            Operation::Div => exact_div(a, b),
+        }
+    }
+
+    fn symbol(self) -> char {
+        match self {
+            Operation::Add => '+',
+            Operation::Sub => '-',
+            Operation::Mul => '*',
+            Operation::Div => '/',
+        }
+    }
+}
+
+/// The monkeys only ever do divisions that come out even
+fn exact_div(a: i64, b: i64) -> SolveResult<i64> {
+    match b {
+        0 => Err("division by zero".into()),
+        b if a % b != 0 => Err(format!("{a} / {b} isn't a whole number").into()),
+        b => Ok(a / b),
+    }
+}
+
+/// A job as it is written, with the names of the monkeys it waits for
+enum RawJob<'a> {
+    Number(i64),
+    Operation(&'a str, Operation, &'a str),
+}
+
+impl<'a> RawJob<'a> {
+    fn parse(i: &'a str) -> IResult<&'a str, Self> {
+        alt((
+            map(nom::character::complete::i64, Self::Number),
+            map(
+                tuple((
+                    alpha1,
+                    delimited(tag(" "), Operation::parse, tag(" ")),
+                    alpha1,
+                )),
+                |(a, op, b)| Self::Operation(a, op, b),
+            ),
+        ))(i)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Job {
+    Number(i64),
+    Operation(usize, Operation, usize),
+}
+
+/// Every monkey's job, the monkeys referred to by their index
+#[derive(Debug, Clone)]
+pub struct Monkeys {
+    names: HashMap<String, usize>,
+    jobs: Vec<Job>,
+}
+
+impl Monkeys {
+    fn new(lines: &[(&str, RawJob)]) -> SolveResult<Self> {
+        let names: HashMap<String, usize> = lines
+            .iter()
+            .enumerate()
+            .map(|(i, (name, _))| (name.to_string(), i))
+            .collect();
+        let mut monkeys = Self {
+            names,
+            jobs: vec![],
+        };
+        for (_, job) in lines {
+            let job = match *job {
+                RawJob::Number(n) => Job::Number(n),
+                RawJob::Operation(a, op, b) => Job::Operation(monkeys.id(a)?, op, monkeys.id(b)?),
+            };
+            monkeys.jobs.push(job);
+        }
+        // a cycle would send the recursion below into a stack overflow
+        monkeys.check_acyclic()?;
+        Ok(monkeys)
+    }
+
+    fn id(&self, name: &str) -> SolveResult<usize> {
+        self.names
+            .get(name)
+            .copied()
+            .ok_or_else(|| format!("there is no monkey {name}").into())
+    }
+
+    fn check_acyclic(&self) -> SolveResult<()> {
+        // 0: not seen, 1: on the current path, 2: done
+        fn visit(jobs: &[Job], state: &mut [u8], m: usize) -> bool {
+            match state[m] {
+                1 => return false,
+                2 => return true,
+                _ => {}
+            }
+            state[m] = 1;
+            let ok = match jobs[m] {
+                Job::Number(_) => true,
+                Job::Operation(a, _, b) => visit(jobs, state, a) && visit(jobs, state, b),
+            };
+            state[m] = 2;
+            ok
+        }
+
+        let mut state = vec![0; self.jobs.len()];
+        match (0..self.jobs.len()).all(|m| visit(&self.jobs, &mut state, m)) {
+            true => Ok(()),
+            false => Err("the monkeys wait for each other in a cycle".into()),
+        }
+    }
+
+    /// The number monkey `m` yells
+    fn eval(&self, m: usize) -> SolveResult<i64> {
+        match self.jobs[m] {
+            Job::Number(n) => Ok(n),
+            Job::Operation(a, op, b) => op.eval(self.eval(a)?, self.eval(b)?),
+        }
+    }
+
+    /// The job of monkey `m` with everything that doesn't depend on the human worked out
+    fn expr(&self, m: usize, human: usize) -> SolveResult<Expr> {
+        if m == human {
+            return Ok(Expr::Human);
+        }
+        match self.jobs[m] {
+            Job::Number(n) => Ok(Expr::Number(n)),
+            Job::Operation(a, op, b) => match (self.expr(a, human)?, self.expr(b, human)?) {
+                (Expr::Number(a), Expr::Number(b)) => Ok(Expr::Number(op.eval(a, b)?)),
+                (a, b) => Ok(Expr::Operation(Box::new(a), op, Box::new(b))),
+            },
+        }
+    }
+
+    /// What the root really checks: that both of its monkeys yell the same
+    pub fn equation(&self) -> SolveResult<Equation> {
+        let human = self.id(HUMAN)?;
+        match self.jobs[self.id(ROOT)?] {
+            Job::Operation(a, _, b) => Ok(Equation {
+                lhs: self.expr(a, human)?,
+                rhs: self.expr(b, human)?,
+            }),
+            Job::Number(_) => Err("root doesn't compare anything".into()),
+        }
     }
+}
+
+/// An expression in the one unknown, the number the human yells
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Expr {
+    Number(i64),
+    Human,
+    Operation(Box<Expr>, Operation, Box<Expr>),
+}
 
-    fn part_2(&self, input: &str) -> String {
-        todo!()
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nested = |e: &Expr| match e {
+            Expr::Operation(..) => format!("({e})"),
+            e => e.to_string(),
+        };
+        match self {
+            Expr::Number(n) => write!(f, "{n}"),
+            Expr::Human => write!(f, "x"),
+            Expr::Operation(a, op, b) => write!(f, "{} {} {}", nested(a), op.symbol(), nested(b)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Equation {
+    lhs: Expr,
+    rhs: Expr,
+}
+
+impl Equation {
+    /// The number the human has to yell.
+    ///
+    /// Going from the root to the human, every operation has the unknown on one side
+    /// and a number on the other, so it is undone on the number on the other side of
+    /// the equation until only the unknown is left.
+    pub fn solve(&self) -> SolveResult<i64> {
+        let (mut unknown, mut value) = match (&self.lhs, &self.rhs) {
+            (e, Expr::Number(n)) | (Expr::Number(n), e) => (e, *n),
+            _ => return Err("the human is on both sides".into()),
+        };
+
+        loop {
+            unknown = match unknown {
+                Expr::Human => return Ok(value),
+                Expr::Number(_) => return Err("the human is on neither side".into()),
+                Expr::Operation(a, op, b) => match (a.as_ref(), op, b.as_ref()) {
+                    (e, op, Expr::Number(n)) => {
+                        value = match op {
+                            Operation::Add => value - n,
+                            Operation::Sub => value + n,
+                            Operation::Mul => exact_div(value, *n)?,
+                            Operation::Div => value * n,
+                        };
+                        e
+                    }
+                    (Expr::Number(n), op, e) => {
+                        value = match op {
+                            Operation::Add => value - n,
+                            Operation::Sub => n - value,
+                            Operation::Mul => exact_div(value, *n)?,
+                            Operation::Div => exact_div(*n, value)?,
+                        };
+                        e
+                    }
+                    _ => return Err("the human is on both sides of an operation".into()),
+                },
+            }
+        }
+    }
+}
+
+impl fmt::Display for Equation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} = {}", self.lhs, self.rhs)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = "";
+    const INPUT: &str = "root: pppw + sjmn
+dbpl: 5
+cczh: sllz + lgvd
+zczc: 2
+ptdq: humn - dvpt
+dvpt: 3
+lfqf: 4
+humn: 5
+ljgn: 2
+sjmn: drzm * dbpl
+sllz: 4
+pppw: cczh / lfqf
+lgvd: ljgn * ptdq
+drzm: hmdt - zczc
+hmdt: 32
+";
+
+    #[test]
+    fn equation() {
+        let monkeys = Day.parse(INPUT).unwrap();
+        let equation = monkeys.equation().unwrap();
+        assert_eq!(equation.to_string(), "(4 + (2 * (x - 3))) / 4 = 150");
+        assert_eq!(equation.solve(), Ok(301));
+    }
+
+    #[test]
+    fn parts() {
+        let monkeys = Day.parse(INPUT).unwrap();
+        assert_eq!(Day.part_1(&monkeys), Ok(152.into()));
+        assert_eq!(Day.part_2(&monkeys), Ok(301.into()));
+    }
+
+    #[test]
+    fn cycle() {
+        let monkeys = Day.parse("root: a + b\na: b * 2\nb: a - 1\n");
+        assert!(monkeys.is_err());
+    }
 }
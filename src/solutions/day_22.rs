@@ -1,19 +1,431 @@
-use crate::Solver;
+use std::collections::VecDeque;
+
+use nom::{
+    branch::alt,
+    character::complete::one_of,
+    combinator::{all_consuming, map},
+    multi::many1,
+    IResult,
+};
+
+use crate::{
+    geom::{Direction4, Vec2, Vec3},
+    grid::Pos,
+    Answer, SolveResult, Solver,
+};
 
 pub struct Day;
 
+solution!(Day);
+
 impl Solver for Day {
-    fn part_1(&self, input: &str) -> String {
-        todo!()
+    type Input = Notes;
+
+    fn parse(&self, input: &str) -> SolveResult<Self::Input> {
+        let (board, path) = input
+            .split_once("\n\n")
+            .ok_or("expected the board and the path split by an empty line")?;
+        let (_, path) = all_consuming(Step::parse_path)(path.trim_end())?;
+        Ok(Notes {
+            board: Board::parse(board)?,
+            path,
+        })
     }
 
-    fn part_2(&self, input: &str) -> String {
-        todo!()
+    fn part_1(&self, notes: &Self::Input) -> SolveResult<Answer> {
+        let board = &notes.board;
+        let end = board.walk(&notes.path, |p, f| Ok(board.flat_wrap(p, f)))?;
+        Ok(end.password().into())
+    }
+
+    fn part_2(&self, notes: &Self::Input) -> SolveResult<Answer> {
+        let cube = Cube::fold(&notes.board)?;
+        let end = notes.board.walk(&notes.path, |p, f| cube.wrap(p, f))?;
+        Ok(end.password().into())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Notes {
+    board: Board,
+    path: Vec<Step>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Forward(u32),
+    Left,
+    Right,
+}
+
+impl Step {
+    fn parse_path(i: &str) -> IResult<&str, Vec<Self>> {
+        many1(alt((
+            map(nom::character::complete::u32, Self::Forward),
+            map(one_of("LR"), |t| match t {
+                'L' => Self::Left,
+                _ => Self::Right,
+            }),
+        )))(i)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Walker {
+    pos: Pos,
+    facing: Direction4,
+}
+
+impl Walker {
+    fn password(&self) -> usize {
+        // the password counts the facings clockwise from right
+        let facing = (self.facing as usize + 3) % 4;
+        1000 * (self.pos.y + 1) + 4 * (self.pos.x + 1) + facing
+    }
+}
+
+const VOID: u8 = b' ';
+const OPEN: u8 = b'.';
+const WALL: u8 = b'#';
+
+/// The map, every row padded with void to the same width
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Board {
+    rows: Vec<Vec<u8>>,
+    width: usize,
+}
+
+impl Board {
+    fn parse(s: &str) -> SolveResult<Self> {
+        let width = s.lines().map(str::len).max().unwrap_or(0);
+        let mut rows = vec![];
+        for (i, l) in s.lines().enumerate() {
+            if let Some(c) = l.bytes().find(|c| ![VOID, OPEN, WALL].contains(c)) {
+                return Err(format!("{:?} on line {} is not a tile", c as char, i + 1).into());
+            }
+            let mut row = l.as_bytes().to_vec();
+            row.resize(width, VOID);
+            rows.push(row);
+        }
+        if !rows.first().is_some_and(|r| r.contains(&OPEN)) {
+            return Err("there is nowhere to start on the top row".into());
+        }
+        Ok(Self { rows, width })
+    }
+
+    fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn tile(&self, Vec2 { x, y }: Pos) -> u8 {
+        self.rows
+            .get(y)
+            .and_then(|r| r.get(x))
+            .copied()
+            .unwrap_or(VOID)
+    }
+
+    /// The neighbouring position on the board, if it isn't void
+    fn step(&self, pos: Pos, facing: Direction4) -> Option<Pos> {
+        let next = pos.checked_add_signed(facing.unit())?;
+        (self.tile(next) != VOID).then_some(next)
+    }
+
+    /// Follows the path from the leftmost open tile of the top row, `wrap` tells where
+    /// a step off the edge of the map ends up
+    fn walk(
+        &self,
+        path: &[Step],
+        wrap: impl Fn(Pos, Direction4) -> SolveResult<(Pos, Direction4)>,
+    ) -> SolveResult<Walker> {
+        let start = self.rows[0]
+            .iter()
+            .position(|&t| t == OPEN)
+            .ok_or("there is nowhere to start on the top row")?;
+        let mut walker = Walker {
+            pos: Vec2::new(start, 0),
+            facing: Direction4::Right,
+        };
+
+        for step in path {
+            match *step {
+                Step::Left => walker.facing = walker.facing.turn_left(),
+                Step::Right => walker.facing = walker.facing.turn_right(),
+                Step::Forward(n) => {
+                    for _ in 0..n {
+                        let (pos, facing) = match self.step(walker.pos, walker.facing) {
+                            Some(pos) => (pos, walker.facing),
+                            None => wrap(walker.pos, walker.facing)?,
+                        };
+                        if self.tile(pos) == WALL {
+                            break;
+                        }
+                        walker = Walker { pos, facing };
+                    }
+                }
+            }
+        }
+        Ok(walker)
+    }
+
+    /// Wrapping around to the other end of the row or column
+    fn flat_wrap(&self, mut pos: Pos, facing: Direction4) -> (Pos, Direction4) {
+        while let Some(back) = self.step(pos, facing.reverse()) {
+            pos = back;
+        }
+        (pos, facing)
+    }
+}
+
+/// A face of the cube, where it is on the board and how it sits in space
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Face {
+    /// Top left corner on the board
+    origin: Pos,
+    /// Points out of the cube
+    normal: Vec3<i32>,
+    /// Where moving right and down on the board goes on this face
+    right: Vec3<i32>,
+    down: Vec3<i32>,
+}
+
+impl Face {
+    /// The direction in space that `facing` goes on this face
+    fn direction(&self, facing: Direction4) -> Vec3<i32> {
+        match facing {
+            Direction4::Right => self.right,
+            Direction4::Down => self.down,
+            Direction4::Left => -self.right,
+            Direction4::Up => -self.down,
+        }
+    }
+
+    /// The face folded over the edge towards `facing`, when this one lies flat
+    fn neighbour(&self, facing: Direction4, origin: Pos) -> Self {
+        let direction = self.direction(facing);
+        // the edge turns down by a right angle, the directions along it stay
+        let turned = |v: Vec3<i32>| match v {
+            v if v == direction => -self.normal,
+            v if v == -direction => self.normal,
+            v => v,
+        };
+        Self {
+            origin,
+            normal: direction,
+            right: turned(self.right),
+            down: turned(self.down),
+        }
+    }
+}
+
+/// The board folded into a cube. It works for any of the nets of a cube: the faces
+/// are folded one after the other from the first, and the edges they meet at follow
+/// from where they end up in space.
+#[derive(Debug, Clone)]
+struct Cube {
+    size: usize,
+    faces: Vec<Face>,
+}
+
+impl Cube {
+    fn fold(board: &Board) -> SolveResult<Self> {
+        let tiles = board.rows.iter().flatten().filter(|&&t| t != VOID).count();
+        let size = ((tiles / 6) as f64).sqrt() as usize;
+        if size == 0 || 6 * size * size != tiles {
+            return Err(format!("{tiles} tiles can't make a cube").into());
+        }
+
+        // faces are found by their position counted in faces, `* size` gives the tiles
+        let is_face = |p: Pos| board.tile(p * size) != VOID;
+        let first = (0..board.width / size)
+            .map(|x| Vec2::new(x, 0))
+            .find(|&p| is_face(p))
+            .ok_or("the top row doesn't start a face")?;
+        let mut faces = vec![Face {
+            origin: first * size,
+            normal: Vec3::new(0, 0, 1),
+            right: Vec3::new(1, 0, 0),
+            down: Vec3::new(0, 1, 0),
+        }];
+        let mut queue = VecDeque::from([(first, faces[0])]);
+        let mut seen = vec![first];
+        while let Some((at, face)) = queue.pop_front() {
+            for facing in Direction4::ALL {
+                let Some(next) = at.checked_add_signed(facing.unit()) else {
+                    continue;
+                };
+                if seen.contains(&next) || !is_face(next) {
+                    continue;
+                }
+                let folded = face.neighbour(facing, next * size);
+                seen.push(next);
+                faces.push(folded);
+                queue.push_back((next, folded));
+            }
+        }
+
+        // with the right number of tiles, full faces leave none of them out
+        let full = |f: &Face| {
+            (0..size).all(|y| (0..size).all(|x| board.tile(f.origin + Vec2::new(x, y)) != VOID))
+        };
+        if !faces.iter().all(full) {
+            return Err("the tiles don't line up into faces".into());
+        }
+        let mut normals: Vec<Vec3<i32>> = faces.iter().map(|f| f.normal).collect();
+        normals.sort();
+        normals.dedup();
+        if faces.len() != 6 || normals.len() != 6 {
+            return Err("the board doesn't fold into a cube".into());
+        }
+        Ok(Self { size, faces })
+    }
+
+    fn face_of(&self, Vec2 { x, y }: Pos) -> SolveResult<&Face> {
+        let n = self.size;
+        self.faces
+            .iter()
+            .find(|f| f.origin == Vec2::new(x - x % n, y - y % n))
+            .ok_or_else(|| format!("({x}, {y}) is not on the cube").into())
+    }
+
+    /// Steps over the edge of a face onto the next one, around the cube.
+    ///
+    /// Positions are the centers of the tiles in space, with the cube spanning `-n..=n`
+    /// on every axis (doubled, so the centers land on integers). Going over the edge
+    /// moves half a tile along the direction of walking and half a tile into the cube,
+    /// and walking goes on into the cube.
+    fn wrap(&self, pos: Pos, facing: Direction4) -> SolveResult<(Pos, Direction4)> {
+        let n = self.size as i32;
+        let from = self.face_of(pos)?;
+        let Vec2 { x: col, y: row } = (pos - from.origin)
+            .try_cast::<i32>()
+            .ok_or("the cube is too large")?;
+        let center =
+            from.normal * n + from.right * (2 * col + 1 - n) + from.down * (2 * row + 1 - n);
+        let direction = from.direction(facing);
+        let center = center + direction - from.normal;
+
+        let to = self
+            .faces
+            .iter()
+            .find(|f| f.normal == direction)
+            .ok_or("the cube is missing a face")?;
+        let local = |axis: Vec3<i32>| ((center.dot(axis) + n - 1) / 2) as usize;
+        let facing = Direction4::ALL
+            .into_iter()
+            .find(|&f| to.direction(f) == -from.normal)
+            .ok_or("the faces don't meet at a right angle")?;
+        Ok((
+            to.origin + Vec2::new(local(to.right), local(to.down)),
+            facing,
+        ))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = "";
+    const INPUT: &str = "        ...#
+        .#..
+        #...
+        ....
+...#.......#
+........#...
+..#....#....
+..........#.
+        ...#....
+        .....#..
+        .#......
+        ......#.
+
+10R5L5R10L4R5L5
+";
+
+    /// The layout of the real inputs, with faces of 3
+    const OTHER_NET: &str = "   ......
+   ......
+   ......
+   ...
+   ...
+   ...
+......
+......
+......
+...
+...
+...
+";
+
+    /// Stepping off every edge tile and straight back again ends up where it started
+    fn check_edges(board: &Board) {
+        let cube = Cube::fold(board).unwrap();
+        for y in 0..board.height() {
+            for x in 0..board.width {
+                for facing in Direction4::ALL {
+                    let pos = Vec2::new(x, y);
+                    if board.tile(pos) == VOID || board.step(pos, facing).is_some() {
+                        continue;
+                    }
+                    let (there, turned) = cube.wrap(pos, facing).unwrap();
+                    assert_ne!(board.tile(there), VOID, "{pos:?} {facing:?}");
+                    assert!(board.step(there, turned.reverse()).is_none());
+                    assert_eq!(
+                        cube.wrap(there, turned.reverse()),
+                        Ok((pos, facing.reverse()))
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn folding() {
+        let notes = Day.parse(INPUT).unwrap();
+        let cube = Cube::fold(&notes.board).unwrap();
+        assert_eq!(cube.size, 4);
+        // the example's walk: from A on face 4 off the right edge to B on face 6
+        assert_eq!(
+            cube.wrap(Vec2::new(11, 5), Direction4::Right),
+            Ok((Vec2::new(14, 8), Direction4::Down))
+        );
+        // and from C at the bottom of face 5 up to D at the bottom of face 2
+        assert_eq!(
+            cube.wrap(Vec2::new(10, 11), Direction4::Down),
+            Ok((Vec2::new(1, 7), Direction4::Up))
+        );
+
+        check_edges(&notes.board);
+        check_edges(&Board::parse(OTHER_NET).unwrap());
+    }
+
+    #[test]
+    fn not_a_cube() {
+        // six faces, but in a strip
+        let board = Board::parse("......\n").unwrap();
+        assert!(Cube::fold(&board).is_err());
+        // not six faces
+        let board = Board::parse("....\n....\n...\n").unwrap();
+        assert!(Cube::fold(&board).is_err());
+        // six faces of 2, but the last one is shifted by half a face
+        let board = Board::parse("  ..\n  ..\n......\n......\n  ..\n  ..\n  ..\n   ..\n").unwrap();
+        assert_eq!(
+            Cube::fold(&board).unwrap_err(),
+            "the tiles don't line up into faces".into()
+        );
+        let notes = Day
+            .parse("  ..\n  ..\n......\n......\n  ..\n  ..\n  ..\n   ..\n\n5")
+            .unwrap();
+        assert!(Day.part_2(&notes).is_err());
+    }
+
+    #[test]
+    fn parts() {
+        let notes = Day.parse(INPUT).unwrap();
+        assert_eq!(
+            notes.path[..4],
+            [Step::Forward(10), Step::Right, Step::Forward(5), Step::Left]
+        );
+        assert_eq!(Day.part_1(&notes), Ok(6032.into()));
+        assert_eq!(Day.part_2(&notes), Ok(5031.into()));
+    }
 }
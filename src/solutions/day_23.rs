@@ -1,19 +1,195 @@
-use crate::Solver;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+use itertools::Itertools;
+
+use crate::{
+    geom::{Direction8, Vec2},
+    Answer, SolveResult, Solver,
+};
 
 pub struct Day;
 
+solution!(Day);
+
 impl Solver for Day {
-    fn part_1(&self, input: &str) -> String {
-        todo!()
+    type Input = Grove;
+
+    fn parse(&self, input: &str) -> SolveResult<Self::Input> {
+        let mut elves = HashSet::new();
+        for (y, l) in input.lines().enumerate() {
+            for (x, c) in l.chars().enumerate() {
+                match c {
+                    '#' => {
+                        elves.insert(Vec2::new(x as isize, y as isize));
+                    }
+                    '.' => {}
+                    c => return Err(format!("{c:?} on line {} is not a tile", y + 1).into()),
+                }
+            }
+        }
+        Ok(Grove { elves, round: 0 })
     }
 
-    fn part_2(&self, input: &str) -> String {
-        todo!()
+    fn part_1(&self, grove: &Self::Input) -> SolveResult<Answer> {
+        let mut grove = grove.clone();
+        for _ in 0..10 {
+            grove.round();
+        }
+        Ok(grove.empty_ground().into())
+    }
+
+    fn part_2(&self, grove: &Self::Input) -> SolveResult<Answer> {
+        let mut grove = grove.clone();
+        while grove.round() {}
+        Ok(grove.round.into())
+    }
+}
+
+/// Column and row, rows grow to the south
+type Pos = Vec2;
+
+/// Where an elf looks before proposing a move, the middle one is where it moves to
+const DIRECTIONS: [[Direction8; 3]; 4] = {
+    use Direction8::*;
+    [
+        [UpLeft, Up, UpRight],
+        [DownLeft, Down, DownRight],
+        [UpLeft, Left, DownLeft],
+        [UpRight, Right, DownRight],
+    ]
+};
+
+/// The elves, wherever they go
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grove {
+    elves: HashSet<Pos>,
+    /// How many rounds have been done, the first direction considered turns with it
+    round: usize,
+}
+
+impl Grove {
+    fn is_free(&self, elf: Pos, d: Direction8) -> bool {
+        !self.elves.contains(&(elf + d.unit()))
+    }
+
+    /// Where the elf at `elf` wants to go, if anywhere
+    fn proposal(&self, elf: Pos) -> Option<Pos> {
+        let alone = Direction8::ALL.into_iter().all(|d| self.is_free(elf, d));
+        if alone {
+            return None;
+        }
+        (0..4)
+            .map(|i| DIRECTIONS[(self.round + i) % 4])
+            .find(|looks| looks.iter().all(|&d| self.is_free(elf, d)))
+            .map(|[_, d, _]| elf + d.unit())
+    }
+
+    /// Lets every elf propose a move and then make it, unless another elf proposed the
+    /// same spot. Returns whether any elf moved.
+    pub fn round(&mut self) -> bool {
+        let proposals: Vec<(Pos, Pos)> = self
+            .elves
+            .iter()
+            .filter_map(|&elf| Some((elf, self.proposal(elf)?)))
+            .collect();
+        let mut wanted: HashMap<Pos, usize> = HashMap::new();
+        for &(_, to) in &proposals {
+            *wanted.entry(to).or_default() += 1;
+        }
+
+        let mut moved = false;
+        for (from, to) in proposals {
+            if wanted[&to] == 1 {
+                self.elves.remove(&from);
+                self.elves.insert(to);
+                moved = true;
+            }
+        }
+        self.round += 1;
+        moved
+    }
+
+    /// The smallest rectangle holding every elf, as the top left and bottom right corner
+    fn bounds(&self) -> (Pos, Pos) {
+        let (x0, x1) = self
+            .elves
+            .iter()
+            .map(|e| e.x)
+            .minmax()
+            .into_option()
+            .unwrap_or_default();
+        let (y0, y1) = self
+            .elves
+            .iter()
+            .map(|e| e.y)
+            .minmax()
+            .into_option()
+            .unwrap_or_default();
+        (Vec2::new(x0, y0), Vec2::new(x1, y1))
+    }
+
+    fn empty_ground(&self) -> usize {
+        let (min, max) = self.bounds();
+        let size = max - min + Vec2::new(1, 1);
+        (size.x * size.y) as usize - self.elves.len()
+    }
+}
+
+/// Draws the rectangle around the elves, handy to follow them round by round
+impl fmt::Display for Grove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (min, max) = self.bounds();
+        for y in min.y..=max.y {
+            let row: String = (min.x..=max.x)
+                .map(|x| {
+                    if self.elves.contains(&Vec2::new(x, y)) {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            writeln!(f, "{row}")?;
+        }
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = "";
+    const INPUT: &str = "....#..
+..###.#
+#...#.#
+.#...##
+#.###..
+##.#.##
+.#..#..
+";
+
+    #[test]
+    fn small() {
+        let mut grove = Day
+            .parse(".....\n..##.\n..#..\n.....\n..##.\n.....\n")
+            .unwrap();
+        assert!(grove.round());
+        assert_eq!(grove.to_string(), "##\n..\n#.\n.#\n#.\n");
+        assert!(grove.round() && grove.round());
+        assert_eq!(
+            grove.to_string(),
+            "..#..\n....#\n#....\n....#\n.....\n..#..\n"
+        );
+        assert!(!grove.round());
+        assert_eq!(grove.round, 4);
+    }
+
+    #[test]
+    fn parts() {
+        let grove = Day.parse(INPUT).unwrap();
+        assert_eq!(Day.part_1(&grove), Ok(110.into()));
+        assert_eq!(Day.part_2(&grove), Ok(20.into()));
+    }
 }
@@ -1,19 +1,206 @@
-use crate::Solver;
+use itertools::Itertools;
+use pathfinding::directed::bfs::bfs;
+
+use crate::{
+    geom::{Direction4, Vec2},
+    Answer, SolveResult, Solver,
+};
 
 pub struct Day;
 
+solution!(Day);
+
 impl Solver for Day {
-    fn part_1(&self, input: &str) -> String {
-        todo!()
+    type Input = Valley;
+
+    fn parse(&self, input: &str) -> SolveResult<Self::Input> {
+        Valley::parse(input)
+    }
+
+    fn part_1(&self, valley: &Self::Input) -> SolveResult<Answer> {
+        let there = valley.crossing(valley.start(), valley.goal(), 0)?;
+        Ok(there.into())
+    }
+
+    fn part_2(&self, valley: &Self::Input) -> SolveResult<Answer> {
+        let (start, goal) = (valley.start(), valley.goal());
+        let there = valley.crossing(start, goal, 0)?;
+        let back = valley.crossing(goal, start, there)?;
+        let again = valley.crossing(start, goal, there + back)?;
+        Ok((there + back + again).into())
+    }
+}
+
+/// Column and row inside the walls, the entrance is in row -1 and the exit in row `height`
+type Pos = Vec2;
+
+/// The blizzards as they are at minute 0, they all wrap around within the walls.
+///
+/// Only the starting positions are kept: a blizzard moving right that is on a tile at
+/// minute `t` started `t` tiles to the left of it, so whether a tile is free at any
+/// minute is a lookup in every direction instead of a simulation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Valley {
+    /// The tiles inside the walls, `.` or the direction of a blizzard
+    tiles: Vec<Vec<u8>>,
+    width: isize,
+    height: isize,
+    /// The columns of the gaps in the top and bottom wall
+    entrance: isize,
+    exit: isize,
+}
+
+impl Valley {
+    fn parse(input: &str) -> SolveResult<Self> {
+        let lines: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+        let [top, inside @ .., bottom] = lines.as_slice() else {
+            return Err("the valley needs a top and a bottom wall".into());
+        };
+        let gap = |wall: &[u8]| {
+            let inside = wall
+                .get(1..wall.len().saturating_sub(1))
+                .unwrap_or_default();
+            match inside.iter().positions(|&t| t == b'.').collect::<Vec<_>>()[..] {
+                [x] => Ok(x as isize),
+                _ => Err(format!(
+                    "expected one gap in the wall {:?}",
+                    String::from_utf8_lossy(wall)
+                )),
+            }
+        };
+
+        let mut tiles = vec![];
+        for (i, row) in inside.iter().enumerate() {
+            match row {
+                [b'#', middle @ .., b'#'] if middle.len() + 2 == top.len() => {
+                    if let Some(&t) = middle.iter().find(|t| !b".<>^v".contains(t)) {
+                        return Err(
+                            format!("{:?} on line {} is not a tile", t as char, i + 2).into()
+                        );
+                    }
+                    tiles.push(middle.to_vec());
+                }
+                _ => return Err(format!("line {} isn't a row between walls", i + 2).into()),
+            }
+        }
+        if tiles.is_empty() || top.len() < 3 {
+            return Err("the valley is empty".into());
+        }
+
+        Ok(Self {
+            width: tiles[0].len() as isize,
+            height: tiles.len() as isize,
+            tiles,
+            entrance: gap(top)?,
+            exit: gap(bottom)?,
+        })
+    }
+
+    fn start(&self) -> Pos {
+        Vec2::new(self.entrance, -1)
+    }
+
+    fn goal(&self) -> Pos {
+        Vec2::new(self.exit, self.height)
+    }
+
+    /// After this many minutes all blizzards are back where they started
+    fn period(&self) -> usize {
+        lcm(self.width as usize, self.height as usize)
+    }
+
+    fn tile(&self, Vec2 { x, y }: Pos) -> u8 {
+        self.tiles[y.rem_euclid(self.height) as usize][x.rem_euclid(self.width) as usize]
+    }
+
+    /// Whether `pos` can be stood on at minute `t`
+    fn is_free(&self, pos: impl Into<Pos>, t: usize) -> bool {
+        let pos = pos.into();
+        if pos == self.start() || pos == self.goal() {
+            return true;
+        }
+        if !(0..self.width).contains(&pos.x) || !(0..self.height).contains(&pos.y) {
+            return false;
+        }
+        // the blizzards that would be here by now, one in every direction
+        let (tx, ty) = (
+            (t % self.width as usize) as isize,
+            (t % self.height as usize) as isize,
+        );
+        self.tile(pos - Vec2::new(tx, 0)) != b'>'
+            && self.tile(pos + Vec2::new(tx, 0)) != b'<'
+            && self.tile(pos - Vec2::new(0, ty)) != b'v'
+            && self.tile(pos + Vec2::new(0, ty)) != b'^'
+    }
+
+    /// The fewest minutes to get from `from` to `to` when setting off at minute `start`.
+    ///
+    /// A breadth first search over where one is and the minute within the period of
+    /// the blizzards, since the valley looks the same again after it.
+    fn crossing(&self, from: Pos, to: Pos, start: usize) -> SolveResult<usize> {
+        let period = self.period();
+        let path = bfs(
+            &(from, start % period),
+            |&(p, t)| {
+                let t = (t + 1) % period;
+                // waiting or a step in any direction
+                std::iter::once(p)
+                    .chain(Direction4::ALL.map(|d| p + d.unit()))
+                    .filter(move |&p| self.is_free(p, t))
+                    .map(move |p| (p, t))
+            },
+            |&(p, _)| p == to,
+        )
+        .ok_or("there is no way through the blizzards")?;
+        Ok(path.len() - 1)
     }
+}
 
-    fn part_2(&self, input: &str) -> String {
-        todo!()
+fn lcm(a: usize, b: usize) -> usize {
+    fn gcd(a: usize, b: usize) -> usize {
+        match b {
+            0 => a,
+            b => gcd(b, a % b),
+        }
     }
+    a / gcd(a, b) * b
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = "";
+    const INPUT: &str = "#.######
+#>>.<^<#
+#.<..<<#
+#>v.><>#
+#<^v^^>#
+######.#
+";
+
+    #[test]
+    fn blizzards() {
+        let valley = Day
+            .parse("#.#####\n#.....#\n#>....#\n#.....#\n#...v.#\n#.....#\n#####.#\n")
+            .unwrap();
+        assert_eq!(valley.period(), 5);
+        // both blizzards meet after three minutes
+        assert!(!valley.is_free((3, 1), 3));
+        assert!(valley.is_free((0, 1), 3) && valley.is_free((3, 3), 3));
+        // and are back after five
+        assert!(!valley.is_free((0, 1), 5) && !valley.is_free((3, 3), 5));
+        // the only way out of the valley is the gaps
+        assert!(valley.is_free((0, -1), 3) && !valley.is_free((1, -1), 3));
+    }
+
+    #[test]
+    fn parts() {
+        let valley = Day.parse(INPUT).unwrap();
+        assert_eq!(valley.period(), 12);
+        assert_eq!(
+            (valley.start(), valley.goal()),
+            (Vec2::new(0, -1), Vec2::new(5, 4))
+        );
+        assert_eq!(Day.part_1(&valley), Ok(18.into()));
+        assert_eq!(Day.part_2(&valley), Ok(54.into()));
+    }
 }
@@ -1,19 +1,234 @@
-use crate::Solver;
+use std::{
+    fmt,
+    iter::Sum,
+    ops::{Add, AddAssign},
+    str::FromStr,
+};
+
+use crate::{error::parse_lines, Answer, SolveError, SolveResult, Solver};
 
 pub struct Day;
 
+// the last day has no second puzzle
+solution!(Day, parts = [Part1]);
+
 impl Solver for Day {
-    fn part_1(&self, input: &str) -> String {
-        todo!()
+    type Input = Vec<Snafu>;
+
+    fn parse(&self, input: &str) -> SolveResult<Self::Input> {
+        parse_lines(input, str::parse)
+    }
+
+    fn part_1(&self, requirements: &Self::Input) -> SolveResult<Answer> {
+        Ok(requirements.iter().sum::<Snafu>().to_string().into())
+    }
+
+    fn part_2(&self, _: &Self::Input) -> SolveResult<Answer> {
+        Err(SolveError::Unimplemented)
+    }
+}
+
+/// A number in balanced base 5, the digits `=`, `-`, `0`, `1` and `2` stand for -2 to 2
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Snafu {
+    /// Least significant first, without zeros at the end, so 0 has no digits at all
+    digits: Vec<i8>,
+}
+
+impl Snafu {
+    fn trim(mut self) -> Self {
+        while self.digits.last() == Some(&0) {
+            self.digits.pop();
+        }
+        self
+    }
+}
+
+impl FromStr for Snafu {
+    type Err = SolveError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err("a SNAFU number needs digits".into());
+        }
+        let digits = s
+            .chars()
+            .rev()
+            .map(|c| match c {
+                '2' => Ok(2),
+                '1' => Ok(1),
+                '0' => Ok(0),
+                '-' => Ok(-1),
+                '=' => Ok(-2),
+                c => Err(format!("{c:?} is not a SNAFU digit")),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { digits }.trim())
     }
+}
+
+impl fmt::Display for Snafu {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.digits.is_empty() {
+            return write!(f, "0");
+        }
+        for d in self.digits.iter().rev() {
+            let c = match d {
+                2 => '2',
+                1 => '1',
+                0 => '0',
+                -1 => '-',
+                -2 => '=',
+                _ => unreachable!("digits stay in -2..=2"),
+            };
+            write!(f, "{c}")?;
+        }
+        Ok(())
+    }
+}
 
-    fn part_2(&self, input: &str) -> String {
-        todo!()
+impl From<i64> for Snafu {
+    fn from(mut n: i64) -> Self {
+        let mut digits = vec![];
+        while n != 0 {
+            // 3 and 4 become -2 and -1 and carry one over
+            let d = (n + 2).rem_euclid(5) - 2;
+            digits.push(d as i8);
+            n = (n - d) / 5;
+        }
+        Self { digits }
+    }
+}
+
+impl From<&Snafu> for i64 {
+    fn from(s: &Snafu) -> Self {
+        s.digits.iter().rev().fold(0, |n, &d| n * 5 + d as i64)
+    }
+}
+
+/// Digit by digit, with a carry like written addition
+impl Add<&Snafu> for &Snafu {
+    type Output = Snafu;
+
+    fn add(self, other: &Snafu) -> Snafu {
+        let len = self.digits.len().max(other.digits.len());
+        let digit = |s: &Snafu, i| s.digits.get(i).copied().unwrap_or(0);
+
+        let mut digits = Vec::with_capacity(len + 1);
+        let mut carry = 0;
+        for i in 0..len {
+            let sum = digit(self, i) + digit(other, i) + carry;
+            let d = (sum + 2).rem_euclid(5) - 2;
+            carry = (sum - d) / 5;
+            digits.push(d);
+        }
+        digits.push(carry);
+        Snafu { digits }.trim()
+    }
+}
+
+impl Add for Snafu {
+    type Output = Snafu;
+
+    fn add(self, other: Snafu) -> Snafu {
+        &self + &other
+    }
+}
+
+impl AddAssign<&Snafu> for Snafu {
+    fn add_assign(&mut self, other: &Snafu) {
+        *self = &*self + other;
+    }
+}
+
+impl<'a> Sum<&'a Snafu> for Snafu {
+    fn sum<I: Iterator<Item = &'a Snafu>>(iter: I) -> Self {
+        iter.fold(Snafu::default(), |mut sum, s| {
+            sum += s;
+            sum
+        })
+    }
+}
+
+impl Sum for Snafu {
+    fn sum<I: Iterator<Item = Snafu>>(iter: I) -> Self {
+        iter.fold(Snafu::default(), |sum, s| sum + s)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use quickcheck::quickcheck;
+
     use super::*;
-    const INPUT: &str = "";
+    const INPUT: &str = "1=-0-2
+12111
+2=0=
+21
+2=01
+111
+20012
+112
+1=-1=
+1-12
+12
+1=
+122
+";
+
+    #[test]
+    fn conversions() {
+        for (n, s) in [
+            (0, "0"),
+            (3, "1="),
+            (8, "2="),
+            (2022, "1=11-2"),
+            (12345, "1-0---0"),
+            (314159265, "1121-1110-1=0"),
+            (-3, "-2"),
+        ] {
+            assert_eq!(Snafu::from(n).to_string(), s);
+            assert_eq!(i64::from(&s.parse::<Snafu>().unwrap()), n);
+        }
+        assert_eq!("002".parse::<Snafu>().unwrap().to_string(), "2");
+        assert!("1=3".parse::<Snafu>().is_err());
+    }
+
+    #[test]
+    fn part_1() {
+        let requirements = Day.parse(INPUT).unwrap();
+        assert_eq!(i64::from(&requirements.iter().sum::<Snafu>()), 4890);
+        assert_eq!(Day.part_1(&requirements), Ok("2=-1=0".into()));
+    }
+
+    // the numbers are kept small enough that neither side can overflow
+    quickcheck! {
+        fn integer_round_trip(n: i32) -> bool {
+            let s = Snafu::from(n as i64);
+            i64::from(&s) == n as i64 && s.to_string().parse::<Snafu>().ok() == Some(s)
+        }
+
+        fn text_round_trip(digits: Vec<u8>) -> bool {
+            let text: String = digits
+                .iter()
+                .map(|d| ['=', '-', '0', '1', '2'][*d as usize % 5])
+                .collect();
+            let text = match text.trim_start_matches('0') {
+                "" => "0",
+                t => t,
+            };
+            text.parse::<Snafu>().map(|s| s.to_string()).ok().as_deref() == Some(text)
+        }
+
+        fn adds_like_integers(a: i32, b: i32) -> bool {
+            let sum = Snafu::from(a as i64) + Snafu::from(b as i64);
+            sum == Snafu::from(a as i64 + b as i64)
+        }
+
+        fn sums_like_integers(numbers: Vec<i32>) -> bool {
+            let snafus: Vec<Snafu> = numbers.iter().map(|&n| Snafu::from(n as i64)).collect();
+            let total: i64 = numbers.iter().map(|&n| n as i64).sum();
+            snafus.iter().sum::<Snafu>() == Snafu::from(total)
+        }
+    }
 }
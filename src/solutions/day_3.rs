@@ -2,32 +2,56 @@ use std::collections::HashSet;
 
 use itertools::Itertools;
 
-use crate::Solver;
+use crate::{
+    error::{parse_lines, SolveError},
+    Answer, SolveResult, Solver,
+};
 
 pub struct Day;
 
+solution!(Day);
+
 impl Solver for Day {
-    fn part_1(&self, input: &str) -> String {
-        solve_1(input).to_string()
+    type Input = Vec<String>;
+
+    fn parse(&self, input: &str) -> SolveResult<Self::Input> {
+        parse_lines(input, |l| match l.chars().find(|c| !c.is_ascii_alphabetic()) {
+            Some(c) => Err(SolveError::new(format!("{c:?} is not an item"))),
+            None => Ok(l.to_string()),
+        })
     }
 
-    fn part_2(&self, input: &str) -> String {
-        solve_2(input).to_string()
+    fn part_1(&self, rucksacks: &Self::Input) -> SolveResult<Answer> {
+        Ok(solve_1(rucksacks)?.into())
+    }
+
+    fn part_2(&self, rucksacks: &Self::Input) -> SolveResult<Answer> {
+        Ok(solve_2(rucksacks)?.into())
     }
 }
 
-fn solve_1(input: &str) -> usize {
-    input.lines().map(|l| compartment_check(l)).sum()
+fn solve_1(rucksacks: &[String]) -> SolveResult<usize> {
+    rucksacks
+        .iter()
+        .enumerate()
+        .map(|(i, l)| compartment_check(l).map_err(|e| e.on_line(i + 1)))
+        .sum()
 }
 
-fn solve_2(input: &str) -> usize {
-    input
-        .lines()
+fn solve_2(rucksacks: &[String]) -> SolveResult<usize> {
+    rucksacks
+        .iter()
+        .map(String::as_str)
         .chunks(3)
         .into_iter()
         .map(|s| s.collect::<Vec<&str>>())
-        .map(|s| duplicates(&s).unwrap())
-        .map(|c| calc_priority(c))
+        .enumerate()
+        .map(|(i, s)| {
+            duplicates(&s)
+                .ok_or_else(|| "group has no common item".into())
+                .and_then(calc_priority)
+                .map_err(|e: SolveError| e.on_line(i * 3 + 1))
+        })
         .sum()
 }
 
@@ -46,18 +70,18 @@ fn duplicates(g: &[&str]) -> Option<char> {
     common.iter().next().cloned()
 }
 
-fn compartment_check(l: &str) -> usize {
+fn compartment_check(l: &str) -> SolveResult<usize> {
     let (left, right) = l.split_at(l.len().div_ceil(2));
 
-    let dup = duplicates(&[left, right]).unwrap();
+    let dup = duplicates(&[left, right]).ok_or("compartments have no common item")?;
     calc_priority(dup)
 }
 
-fn calc_priority(c: char) -> usize {
-    if c.is_lowercase() {
-        (c as u8 - b'a' + 1) as usize
-    } else {
-        (c as u8 - b'A' + 27) as usize
+fn calc_priority(c: char) -> SolveResult<usize> {
+    match c {
+        'a'..='z' => Ok((c as u8 - b'a' + 1) as usize),
+        'A'..='Z' => Ok((c as u8 - b'A' + 27) as usize),
+        _ => Err(SolveError::new(format!("{c:?} is not an item"))),
     }
 }
 
@@ -77,12 +101,12 @@ mod tests {
     #[test_case(20, "ttgJtRGJQctTZtZT")]
     #[test_case(19, "CrZsJsPPZsGzwwsLwLmpwMDw")]
     fn test_compartment_check(prio: usize, line: &str) {
-        assert_eq!(prio, compartment_check(line))
+        assert_eq!(Ok(prio), compartment_check(line))
     }
 
     #[test]
     fn part1() {
-        let sum: usize = TEST.lines().map(|l| compartment_check(l)).sum();
+        let sum: usize = TEST.lines().map(|l| compartment_check(l).unwrap()).sum();
         assert_eq!(sum, 157);
     }
 
@@ -94,7 +118,7 @@ mod tests {
             .into_iter()
             .map(|s| s.collect::<Vec<&str>>())
             .map(|s| duplicates(&s).unwrap())
-            .map(|c| calc_priority(c))
+            .map(|c| calc_priority(c).unwrap())
             .sum();
         assert_eq!(sum, 70)
     }
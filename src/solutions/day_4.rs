@@ -1,21 +1,29 @@
 use itertools::Itertools;
 
-use crate::Solver;
+use crate::{error::parse_lines, Answer, SolveResult, Solver};
 
 pub struct Day;
 
+solution!(Day);
+
 impl Solver for Day {
-    fn part_1(&self, input: &str) -> String {
-        solve_1(input).to_string()
+    type Input = Vec<(Assignment, Assignment)>;
+
+    fn parse(&self, input: &str) -> SolveResult<Self::Input> {
+        parse_lines(input, parse_line)
+    }
+
+    fn part_1(&self, pairs: &Self::Input) -> SolveResult<Answer> {
+        Ok(solve_1(pairs).into())
     }
 
-    fn part_2(&self, input: &str) -> String {
-        solve_2(input).to_string()
+    fn part_2(&self, pairs: &Self::Input) -> SolveResult<Answer> {
+        Ok(solve_2(pairs).into())
     }
 }
 
 #[derive(Debug, PartialEq, PartialOrd, Eq, Ord)]
-struct Assignment(i64, i64);
+pub struct Assignment(i64, i64);
 
 impl Assignment {
     fn fully_contained(&self, rhs: &Assignment) -> bool {
@@ -42,27 +50,23 @@ impl TryFrom<&str> for Assignment {
     }
 }
 
-fn parse_line(l: &str) -> (Assignment, Assignment) {
-    l.split(',')
-        .map(|a| Assignment::try_from(a).unwrap())
+fn parse_line(l: &str) -> SolveResult<(Assignment, Assignment)> {
+    let assignments: Vec<Assignment> = l
+        .split(',')
+        .map(Assignment::try_from)
+        .collect::<Result<_, _>>()?;
+    Ok(assignments
+        .into_iter()
         .sorted()
         .collect_tuple()
-        .unwrap()
+        .ok_or("expected exactly two assignments")?)
 }
 
-fn solve_1(input: &str) -> usize {
-    input
-        .lines()
-        .map(|l| parse_line(l))
-        .filter(|(x, y)| x.fully_contained(y))
-        .count()
+fn solve_1(pairs: &[(Assignment, Assignment)]) -> usize {
+    pairs.iter().filter(|(x, y)| x.fully_contained(y)).count()
 }
-fn solve_2(input: &str) -> usize {
-    input
-        .lines()
-        .map(|l| parse_line(l))
-        .filter(|(x, y)| x.overlap(y))
-        .count()
+fn solve_2(pairs: &[(Assignment, Assignment)]) -> usize {
+    pairs.iter().filter(|(x, y)| x.overlap(y)).count()
 }
 
 #[cfg(test)]
@@ -99,11 +103,11 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        assert_eq!(solve_1(&TEST), 2)
+        assert_eq!(solve_1(&Day.parse(TEST).unwrap()), 2)
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(solve_2(&TEST), 4)
+        assert_eq!(solve_2(&Day.parse(TEST).unwrap()), 4)
     }
 }
@@ -1,53 +1,73 @@
 use itertools::Itertools;
 
-use crate::Solver;
+use crate::{Answer, SolveError, SolveResult, Solver};
 
 pub struct Day;
 
+solution!(Day);
+
 impl Solver for Day {
-    fn part_1(&self, input: &str) -> String {
-        let (s, m) = prepare_input(input);
-        solve_part1(&mut s.clone(), &m)
+    type Input = (Stacks, Vec<Move>);
+
+    fn parse(&self, input: &str) -> SolveResult<Self::Input> {
+        prepare_input(input)
+    }
+
+    fn part_1(&self, (s, m): &Self::Input) -> SolveResult<Answer> {
+        Ok(solve_part1(&mut s.clone(), m)?.into())
     }
 
-    fn part_2(&self, input: &str) -> String {
-        let (s, m) = prepare_input(input);
-        solve_part2(&mut s.clone(), &m)
+    fn part_2(&self, (s, m): &Self::Input) -> SolveResult<Answer> {
+        Ok(solve_part2(&mut s.clone(), m)?.into())
     }
 }
 
-fn split_input(input: &str) -> (Vec<&str>, Vec<&str>) {
+fn split_input(input: &str) -> SolveResult<(Vec<&str>, Vec<&str>)> {
     let (stack, moves) = input
         .split_once("\n\n")
-        .expect("there should be an empty line!");
+        .ok_or("there should be an empty line between stacks and moves")?;
     let (stack, moves) = (stack.lines().collect(), moves.lines().collect());
 
-    (stack, moves)
+    Ok((stack, moves))
 }
 
-fn prepare_input(input: &str) -> (Stacks, Vec<&str>) {
-    let (s, m) = split_input(input);
-    let s = parse_stack(&s);
-    (s, m)
+fn prepare_input(input: &str) -> SolveResult<(Stacks, Vec<Move>)> {
+    let (s, m) = split_input(input)?;
+    // moves start after the stack drawing and the empty line
+    let offset = s.len() + 2;
+    let s = parse_stack(&s)?;
+    let m = m
+        .iter()
+        .enumerate()
+        .map(|(i, &l)| Move::try_from(l).map_err(|e| e.on_line(offset + i)))
+        .collect::<SolveResult<_>>()?;
+    Ok((s, m))
 }
 #[derive(Debug, Default)]
-struct Move {
+pub struct Move {
     src: usize,
     dst: usize,
     cnt: usize,
 }
 
 impl TryFrom<&str> for Move {
-    type Error = &'static str;
+    type Error = SolveError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         let it = value
-            .split(" ")
+            .split(' ')
             .skip(1)
             .step_by(2)
-            .map(|s| s.parse::<usize>().expect("failed to parse to int"));
+            .map(|s| s.parse::<usize>().map_err(SolveError::new));
 
-        let (cnt, src, dst) = it.tuples().next().expect("something went wrong");
+        let (cnt, src, dst) = it
+            .collect_tuple()
+            .ok_or_else(|| format!("{value:?} is not a valid move"))?;
+        let (cnt, src, dst) = (cnt?, src?, dst?);
+
+        if src == 0 || dst == 0 {
+            return Err("stacks are numbered starting from 1".into());
+        }
 
         Ok(Self {
             src: src - 1,
@@ -58,7 +78,7 @@ impl TryFrom<&str> for Move {
 }
 
 #[derive(Debug, Default, Clone)]
-struct Stacks {
+pub struct Stacks {
     stacks: Vec<Vec<char>>,
 }
 
@@ -67,75 +87,96 @@ impl Stacks {
         Self { stacks }
     }
 
-    fn move_single(&mut self, order: &Move) {
-        assert!(order.src < self.stacks.len() && order.dst < self.stacks.len());
+    fn check(&self, order: &Move) -> SolveResult<()> {
+        let len = self.stacks.len();
+        if order.src >= len || order.dst >= len {
+            return Err(format!("there are only {len} stacks").into());
+        }
+        if self.stacks[order.src].len() < order.cnt {
+            return Err(
+                format!("stack {} has less than {} crates", order.src + 1, order.cnt).into(),
+            );
+        }
+        Ok(())
+    }
+
+    fn move_single(&mut self, order: &Move) -> SolveResult<()> {
+        self.check(order)?;
 
         for _ in 0..order.cnt {
-            let c = self.stacks[order.src].pop().expect("stack already empty");
+            let c = self.stacks[order.src].pop().ok_or("stack already empty")?;
             self.stacks[order.dst].push(c);
         }
+        Ok(())
     }
 
-    fn move_multiple(&mut self, order: &Move) {
+    fn move_multiple(&mut self, order: &Move) -> SolveResult<()> {
+        self.check(order)?;
+
         let src = &mut self.stacks[order.src];
         let drained: Vec<_> = src.drain(src.len() - order.cnt..).collect();
         for c in drained {
             self.stacks[order.dst].push(c)
         }
+        Ok(())
     }
 
     fn _draw(&self) {
         self.stacks.iter().for_each(|s| {
             s.iter().for_each(|c| print!("{}", c));
-            println!("");
+            println!();
         });
     }
 
-    fn get_top(&self) -> String {
+    fn get_top(&self) -> SolveResult<String> {
         self.stacks
             .iter()
-            .map(|s| s.last().expect("stack should not be empty"))
+            .enumerate()
+            .map(|(i, s)| {
+                s.last()
+                    .ok_or_else(|| format!("stack {} is empty", i + 1).into())
+            })
             .collect()
     }
 }
 
-fn parse_stack(input: &[&str]) -> Stacks {
+fn parse_stack(input: &[&str]) -> SolveResult<Stacks> {
     let mut it = input.iter().rev();
-    let &last = it.next().expect("Empty input");
+    let &last = it.next().ok_or("Empty input")?;
 
     let mut stacks: Vec<Vec<char>> = vec![];
     last.split_ascii_whitespace()
         .for_each(|_| stacks.push(Default::default()));
 
-    for &l in it {
-        l.chars()
-            .chunks(4)
-            .into_iter()
-            .map(|c| c.skip(1).next().expect("expected another char"))
-            .enumerate()
-            .for_each(|(i, c)| {
-                if !c.is_whitespace() {
-                    stacks[i].push(c)
-                }
-            });
+    for (n, &l) in it.enumerate() {
+        let line = input.len() - 1 - n;
+        for (i, mut c) in l.chars().chunks(4).into_iter().enumerate() {
+            let c = c
+                .nth(1)
+                .ok_or_else(|| SolveError::at(line, "expected another char"))?;
+            if !c.is_whitespace() {
+                stacks
+                    .get_mut(i)
+                    .ok_or_else(|| SolveError::at(line, "more crates than stacks"))?
+                    .push(c)
+            }
+        }
     }
 
-    Stacks::new(stacks)
+    Ok(Stacks::new(stacks))
 }
 
-fn solve_part1(s: &mut Stacks, m: &[&str]) -> String {
-    for &l in m {
-        let cmd = Move::try_from(l).expect("Should be a valid move");
-        s.move_single(&cmd);
+fn solve_part1(s: &mut Stacks, m: &[Move]) -> SolveResult<String> {
+    for cmd in m {
+        s.move_single(cmd)?;
     }
 
     s.get_top()
 }
 
-fn solve_part2(s: &mut Stacks, m: &[&str]) -> String {
-    for &l in m {
-        let cmd = Move::try_from(l).expect("Should be a valid move");
-        s.move_multiple(&cmd);
+fn solve_part2(s: &mut Stacks, m: &[Move]) -> SolveResult<String> {
+    for cmd in m {
+        s.move_multiple(cmd)?;
     }
     s.get_top()
 }
@@ -145,18 +186,18 @@ mod tests {
     use super::*;
 
     const TEST: &str = r"    [D]    
-    [N] [C]    
-    [Z] [M] [P]
-     1   2   3 
-    
-    move 1 from 2 to 1
-    move 3 from 1 to 3
-    move 2 from 2 to 1
-    move 1 from 1 to 2";
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2";
 
     #[test]
     fn test_split_input() {
-        let (s, m) = split_input(TEST);
+        let (s, m) = split_input(TEST).unwrap();
         s.iter().for_each(|l| println!("{}", l));
         println!("---------------------------------");
         println!("---------------------------------");
@@ -165,21 +206,21 @@ mod tests {
 
     #[test]
     fn test_parse_stack() {
-        let (stack, _) = split_input(TEST);
-        parse_stack(&stack);
+        let (stack, _) = split_input(TEST).unwrap();
+        parse_stack(&stack).unwrap();
     }
 
     #[test]
     fn test_solve_part_1() {
-        let (mut s, m) = prepare_input(TEST);
+        let (mut s, m) = prepare_input(TEST).unwrap();
         let tops = solve_part1(&mut s, &m);
-        assert_eq!(tops, "CMZ");
+        assert_eq!(tops, Ok("CMZ".to_string()));
     }
 
     #[test]
     fn test_solve_part_2() {
-        let (mut s, m) = prepare_input(TEST);
+        let (mut s, m) = prepare_input(TEST).unwrap();
         let tops = solve_part2(&mut s, &m);
-        assert_eq!(tops, "MCD")
+        assert_eq!(tops, Ok("MCD".to_string()))
     }
 }
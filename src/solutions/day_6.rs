@@ -1,4 +1,4 @@
-use crate::Solver;
+use crate::{Answer, SolveResult, Solver};
 use itertools::Itertools;
 
 const START_OF_PACKET_LEN: usize = 4;
@@ -6,17 +6,25 @@ const START_OF_MSG_LEN: usize = 14;
 
 pub struct Day;
 
+solution!(Day);
+
 impl Solver for Day {
-    fn part_1(&self, input: &str) -> String {
+    type Input = String;
+
+    fn parse(&self, input: &str) -> SolveResult<Self::Input> {
+        Ok(input.trim_end().to_string())
+    }
+
+    fn part_1(&self, input: &Self::Input) -> SolveResult<Answer> {
         let (_, s) = find_marker(input, START_OF_PACKET_LEN)
-            .expect("There has to bee a marker in the input");
-        s.to_string()
+            .ok_or("There has to be a start-of-packet marker in the input")?;
+        Ok(s.into())
     }
 
-    fn part_2(&self, input: &str) -> String {
-        let (_, s) =
-            find_marker(input, START_OF_MSG_LEN).expect("There has to bee a marker in the input");
-        s.to_string()
+    fn part_2(&self, input: &Self::Input) -> SolveResult<Answer> {
+        let (_, s) = find_marker(input, START_OF_MSG_LEN)
+            .ok_or("There has to be a start-of-message marker in the input")?;
+        Ok(s.into())
     }
 }
 
@@ -1,46 +1,42 @@
 use std::collections::HashMap;
 
-use crate::Solver;
+use crate::{error::parse_lines, Answer, SolveResult, Solver};
 
 pub struct Day;
 
-impl Solver for Day {
-    fn part_1(&self, input: &str) -> String {
-        let entries = crawler(input);
+solution!(Day);
 
-        let dt = total_size(&entries);
-        for x in dt.iter().sorted_by(|x, y| x.1.cmp(y.1)) {
-            println!("{:?}", x)
-        }
+impl Solver for Day {
+    type Input = DirSizeTree;
 
-        let result: usize = dt
-            .values()
-            .filter(|&&size| dbg!(size) < 100_000)
-            .sum1()
-            .unwrap();
-        result.to_string()
+    fn parse(&self, input: &str) -> SolveResult<Self::Input> {
+        Ok(total_size(&crawler(input)?))
     }
 
-    fn part_2(&self, input: &str) -> String {
-        let entries = crawler(input);
-
-        let dt = total_size(&entries);
+    fn part_1(&self, dt: &Self::Input) -> SolveResult<Answer> {
+        let result: usize = dt.values().filter(|&&size| size < 100_000).sum();
+        Ok(result.into())
+    }
 
-        let total_used: usize = *dt.get(&Utf8PathBuf::from("/")).unwrap();
+    fn part_2(&self, dt: &Self::Input) -> SolveResult<Answer> {
+        let total_used: usize = *dt
+            .get(&Utf8PathBuf::from("/"))
+            .ok_or("no files found below /")?;
 
-        let free = DISK_SIZE - total_used;
+        let free = DISK_SIZE
+            .checked_sub(total_used)
+            .ok_or("more space used than the disk has")?;
         if free > MIN_FREE {
-            return free.to_string();
+            return Ok(free.into());
         }
         let min = MIN_FREE - free;
         let result = dt
             .values()
             .sorted()
-            .filter(|&&val| val > min)
-            .next()
-            .unwrap();
+            .find(|&&val| val > min)
+            .ok_or("no directory is large enough")?;
 
-        result.to_string()
+        Ok((*result).into())
     }
 }
 
@@ -140,14 +136,13 @@ fn parse_line(i: &str) -> IResult<&str, Line> {
 type EntryTree = HashMap<Utf8PathBuf, Entry>;
 type DirSizeTree = HashMap<Utf8PathBuf, usize>;
 
-fn crawler(input: &str) -> EntryTree {
+fn crawler(input: &str) -> SolveResult<EntryTree> {
     let mut pwd: Utf8PathBuf = Utf8PathBuf::from("/");
 
-    let lines = input
-        .lines()
-        .map(|l| all_consuming(parse_line)(l).finish().unwrap().1);
+    let lines = parse_lines(input, |l| Ok(all_consuming(parse_line)(l).finish()?.1))?;
 
-    lines
+    Ok(lines
+        .into_iter()
         .filter_map(|l| {
             match l {
                 Line::Command(Command::Ls) | Line::Entry(Entry::Dir(_)) => None,
@@ -178,7 +173,7 @@ fn crawler(input: &str) -> EntryTree {
                 }
             }
         })
-        .collect()
+        .collect())
 }
 
 fn update_parent_size(f: &File, tree: &mut DirSizeTree) {
@@ -236,7 +231,7 @@ $ ls
 
     #[test]
     fn test_crawler() {
-        let entries = crawler(TEST);
+        let entries = crawler(TEST).unwrap();
         entries
             .iter()
             .sorted_by(|b, a| b.0.cmp(a.0))
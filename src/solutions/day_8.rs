@@ -1,140 +1,101 @@
-use std::fmt::Display;
-
-use crate::Solver;
-use itertools::*;
+use crate::{
+    geom::Direction4,
+    grid::{Grid, Pos},
+    verbose, Answer, SolveResult, Solver,
+};
 
 pub struct Day;
 
-// type Forrest = Ve
+solution!(Day);
 
 impl Solver for Day {
-    fn part_1(&self, input: &str) -> String {
-        let forest = parse_forest(input);
-        let mut visible = prepare_visible(&forest);
-        count_visible(&visible);
-
-        {
-            let f_it = forest.iter();
-            let v_it = visible.iter_mut();
-            search(f_it, v_it);
-        }
-
-        count_visible(&visible);
-
-        {
-            let f_it = forest.iter();
-            let v_it = visible.iter_mut();
+    type Input = Grid<u8>;
 
-            search_rev(f_it, v_it);
-        }
-        count_visible(&visible);
-
-        print_forest(&forest);
-
-        let forest = transpose2(forest);
-        let mut visible = transpose2(visible);
-
-        {
-            let f_it = forest.iter();
-            let v_it = visible.iter_mut();
-            search(f_it, v_it);
-        }
-
-        count_visible(&visible);
-
-        {
-            let f_it = forest.iter();
-            let v_it = visible.iter_mut();
-
-            search_rev(f_it, v_it);
-        }
-        print_forest(&forest);
-
-        count_visible(&visible).to_string()
-    }
-
-    fn part_2(&self, input: &str) -> String {
-        todo!()
+    fn parse(&self, input: &str) -> SolveResult<Self::Input> {
+        Grid::parse(input, |c| match c.to_digit(10) {
+            Some(h) => Ok(h as u8),
+            None => Err(format!("{c:?} is not a tree").into()),
+        })
     }
-}
 
-fn print_forest<T: Display>(f: &Vec<Vec<T>>) {
-    for l in f {
-        for t in l {
-            print!("{}", t);
+    fn part_1(&self, forest: &Self::Input) -> SolveResult<Answer> {
+        let visible = find_visible(forest);
+        let count = visible.iter().filter(|&&v| v).count();
+        if verbose() {
+            eprintln!("{forest}");
+            eprintln!("{}", visible.map(|&v| if v { '#' } else { '.' }));
+            eprintln!("{:6} visible", count);
         }
-        println!();
+        Ok(count.into())
     }
-}
 
-fn transpose2<T>(v: Vec<Vec<T>>) -> Vec<Vec<T>> {
-    assert!(!v.is_empty());
-    let len = v[0].len();
-    let mut iters: Vec<_> = v.into_iter().map(|n| n.into_iter()).collect();
-    (0..len)
-        .map(|_| {
-            iters
-                .iter_mut()
-                .map(|n| n.next().unwrap())
-                .collect::<Vec<T>>()
-        })
-        .collect()
-}
-
-fn search_rev(f_it: std::slice::Iter<Vec<u8>>, v_it: std::slice::IterMut<Vec<bool>>) {
-    for (l_f, l_v) in f_it.zip(v_it) {
-        let mut largest = 0;
-        for (&t, v) in l_f.iter().rev().zip(l_v.iter_mut().rev()) {
-            if t > largest {
-                largest = t;
-                *v = true;
-            }
+    fn part_2(&self, forest: &Self::Input) -> SolveResult<Answer> {
+        let (score, p) = forest
+            .positions()
+            .map(|p| (scenic_score(forest, p), p))
+            .max()
+            .ok_or("the forest is empty")?;
+        if verbose() {
+            eprintln!(
+                "the tree in column {}, row {} has a scenic score of {score}",
+                p.x, p.y
+            );
         }
+        Ok(score.into())
     }
 }
 
-fn search(f_it: std::slice::Iter<Vec<u8>>, v_it: std::slice::IterMut<Vec<bool>>) {
-    for (l_f, l_v) in f_it.zip(v_it) {
-        let mut largest = 0;
-        for (&t, v) in l_f.iter().zip(l_v.iter_mut()) {
-            if t > largest {
-                largest = t;
-                *v = true;
+/// Which trees can be seen from outside the forest.
+///
+/// Every row is swept from both sides and every column from the top and the bottom,
+/// a tree is visible when it is taller than all trees the sweep has passed.
+fn find_visible(forest: &Grid<u8>) -> Grid<bool> {
+    let (width, height) = (forest.width(), forest.height());
+    let mut visible = Grid::new(width, height, false);
+
+    let mut sweep = |line: &mut dyn Iterator<Item = (usize, usize)>| {
+        let mut tallest = None;
+        for p in line {
+            let t = Some(forest[p]);
+            if t > tallest {
+                tallest = t;
+                visible[p] = true;
             }
         }
+    };
+    for y in 0..height {
+        sweep(&mut (0..width).map(|x| (x, y)));
+        sweep(&mut (0..width).rev().map(|x| (x, y)));
     }
-}
-
-fn count_visible(v: &Vec<Vec<bool>>) -> usize {
-    let r = v.iter().flat_map(|l| l.iter()).filter(|b| **b).count();
-    println!("{:6} visible", r);
-    r
-}
-
-fn prepare_visible(forest: &Vec<Vec<u8>>) -> Vec<Vec<bool>> {
-    let l_len = forest.iter().next().unwrap().len();
-    let mut visible: Vec<Vec<bool>> = Vec::with_capacity(forest.len());
-    for _ in 0..forest.len() {
-        let mut v = Vec::with_capacity(l_len);
-        for _ in 0..l_len {
-            v.push(false);
-        }
-        visible.push(v);
+    for x in 0..width {
+        sweep(&mut (0..height).map(|y| (x, y)));
+        sweep(&mut (0..height).rev().map(|y| (x, y)));
     }
     visible
 }
 
-fn parse_forest(input: &str) -> Vec<Vec<u8>> {
-    let mut forest: Vec<Vec<u8>> = input
-        .lines()
-        .map(|l| l.as_bytes().iter().map(|b| *b).collect())
-        .collect();
-    forest
+/// How many trees can be seen from the tree at `pos` in each direction, multiplied
+fn scenic_score(forest: &Grid<u8>, pos: Pos) -> usize {
+    let height = forest[pos];
+    Direction4::ALL
+        .into_iter()
+        .map(|d| {
+            // the view stops at the first tree that is at least as tall, which is still seen
+            let mut seen = 0;
+            for p in forest.ray(pos, d) {
+                seen += 1;
+                if forest[p] >= height {
+                    break;
+                }
+            }
+            seen
+        })
+        .product()
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{Day, Solver};
+    use super::{find_visible, scenic_score, Day, Solver};
     use assert_ok::assert_ok;
     const TEST: &str = r"30373
 25512
@@ -144,7 +105,33 @@ mod tests {
 
     #[test]
     fn solver_part_1() {
-        let result: String = Day.part_1(TEST);
-        assert_eq!(&result, "21")
+        let result = Day.part_1(&Day.parse(TEST).unwrap());
+        assert_eq!(result, Ok(21usize.into()))
+    }
+
+    #[test]
+    fn visible() {
+        let forest = Day.parse(TEST).unwrap();
+        let visible = find_visible(&forest);
+        // the edge is always visible, inside it are the trees from the puzzle text
+        let interior: Vec<&[bool]> = (1..4).map(|y| &visible.row(y)[1..4]).collect();
+        assert_eq!(
+            interior,
+            [
+                [true, true, false],
+                [true, false, true],
+                [false, true, false]
+            ]
+        );
+    }
+
+    #[test]
+    fn solver_part_2() {
+        let forest = Day.parse(TEST).unwrap();
+        assert_eq!(scenic_score(&forest, (2, 1).into()), 4);
+        assert_eq!(scenic_score(&forest, (2, 3).into()), 8);
+        assert_eq!(scenic_score(&forest, (0, 2).into()), 0);
+        let result = Day.part_2(&forest);
+        assert_eq!(result, Ok(8usize.into()))
     }
 }
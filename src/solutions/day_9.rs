@@ -1,10 +1,5 @@
-use std::{
-    collections::HashSet,
-    ops::{Add, AddAssign, Deref, DerefMut, Sub, SubAssign},
-    time::Duration,
-};
+use std::{collections::HashSet, time::Duration};
 
-use itertools::Itertools;
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -14,208 +9,54 @@ use nom::{
     IResult,
 };
 
-use crate::Solver;
+use crate::{
+    error::parse_lines,
+    geom::{Direction4, Vec2},
+    grid::Grid,
+    Answer, SolveResult, Solver,
+};
 
 pub struct Day;
 
-impl Solver for Day {
-    fn part_1(&self, input: &str) -> String {
-        series_of_motions(input, 1).to_string()
-    }
-
-    fn part_2(&self, input: &str) -> String {
-        series_of_motions(input, 9).to_string()
-    }
-}
-
-#[derive(Debug)]
-struct Grid {
-    size: (usize, usize),
-    origin: Point,
-    field: Vec<char>,
-}
-
-impl Grid {
-    fn new(x: usize, y: usize) -> Self {
-        let field: Vec<char> = vec!['.'; x * y];
-        Self {
-            size: (x, y),
-            origin: Point {
-                x: x as isize / 2,
-                y: y as isize / 2,
-            },
-            field,
-        }
-    }
-
-    fn clear(&mut self) {
-        self.field.iter_mut().for_each(|c| *c = '.');
-    }
-
-    fn get_index(&self, p: &Point) -> usize {
-        let r = self.origin + *p;
-        let x = usize::try_from(r.x).unwrap();
-        let y = usize::try_from(r.y).unwrap();
+solution!(Day);
 
-        x * self.size.0 + y
-    }
-
-    fn set_coord(&mut self, p: &Point, new: char) -> Option<()> {
-        let index = self.get_index(p);
-        self.field.get_mut(index).map(|c| *c = new);
+impl Solver for Day {
+    type Input = Vec<Movement>;
 
-        Some(())
+    fn parse(&self, input: &str) -> SolveResult<Self::Input> {
+        parse_lines(input, |l| Ok(all_consuming(parse_movement)(l)?.1))
     }
 
-    fn set_relative(&mut self, p: &Point, rel: &Point, new: char) {
-        let set = *p + *rel;
-        self.set_coord(&set, new);
+    fn part_1(&self, moves: &Self::Input) -> SolveResult<Answer> {
+        Ok(series_of_motions(moves, 1).into())
     }
 
-    fn draw(&self) -> String {
-        let mut result = String::with_capacity((self.size.0 * self.size.1) as _);
-        self.field
-            .iter()
-            .rev()
-            .chunks(self.size.0 as _)
-            .into_iter()
-            .for_each(|chunk| {
-                let mut s: String = chunk.collect();
-                result.push_str(&s);
-                result.push('\n');
-            });
-        result
+    fn part_2(&self, moves: &Self::Input) -> SolveResult<Answer> {
+        Ok(series_of_motions(moves, 9).into())
     }
 }
 
 const DRAW: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
 
 fn draw_field(r: &Rope) {
-    let span = (UR - LL).abs();
-    let mut g = Grid::new(span.x.try_into().unwrap(), span.y.try_into().unwrap());
-
-    for (knot, new) in r.knots.iter().zip(DRAW.chars()) {
-        g.set_coord(knot, new);
-    }
-    print!("{}", g.draw());
-    println!("");
-}
-
-#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
-struct Point {
-    x: isize,
-    y: isize,
-}
-
-impl From<(isize, isize)> for Point {
-    fn from(value: (isize, isize)) -> Self {
-        Self {
-            x: value.0,
-            y: value.1,
-        }
-    }
-}
-
-impl Point {
-    fn abs(&self) -> Self {
-        Self {
-            x: self.x.abs(),
-            y: self.y.abs(),
-        }
-    }
-
-    fn step_head(&mut self, step: Direction) {
-        *self += step.into();
-    }
-
-    fn step_tail(&mut self, step: Direction, head: &Point) {
-        let diff = (*head - *self);
-
-        // I've lifted this from fasterthanli.me
-        let dp = match (diff.x, diff.y) {
-            // overlapping
-            (0, 0) => (0, 0),
-            // touching up/left/down/right
-            (0, 1) | (1, 0) | (0, -1) | (-1, 0) => (0, 0),
-            // touching diagonally
-            (1, 1) | (1, -1) | (-1, 1) | (-1, -1) => (0, 0),
-            // need to move up/left/down/right
-            (0, 2) => (0, 1),
-            (0, -2) => (0, -1),
-            (2, 0) => (1, 0),
-            (-2, 0) => (-1, 0),
-            // need to move to the right diagonally
-            (2, 1) => (1, 1),
-            (2, -1) => (1, -1),
-            // need to move to the left diagonally
-            (-2, 1) => (-1, 1),
-            (-2, -1) => (-1, -1),
-            // need to move up/down diagonally
-            (1, 2) => (1, 1),
-            (-1, 2) => (-1, 1),
-            (1, -2) => (1, -1),
-            (-1, -2) => (-1, -1),
-            // 🆕 need to move diagonally
-            (-2, -2) => (-1, -1),
-            (-2, 2) => (-1, 1),
-            (2, -2) => (1, -1),
-            (2, 2) => (1, 1),
-            _ => panic!("unhandled case: tail - head = {diff:?}"),
-        }
-        .into();
-
-        *self += dp;
-    }
-}
-
-impl From<Direction> for Point {
-    fn from(value: Direction) -> Self {
-        match value {
-            Direction::Up => Point { x: 0, y: -1 },
-            Direction::Down => Point { x: 0, y: 1 },
-            Direction::Left => Point { x: -1, y: 0 },
-            Direction::Right => Point { x: 1, y: 0 },
-        }
-    }
-}
-
-impl AddAssign for Point {
-    fn add_assign(&mut self, rhs: Self) {
-        *self = *self + rhs;
-    }
-}
-
-impl SubAssign for Point {
-    fn sub_assign(&mut self, rhs: Self) {
-        *self = *self - rhs;
-    }
-}
-
-impl Add for Point {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-        }
-    }
-}
-
-impl Sub for Point {
-    type Output = Self;
-
-    fn sub(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
+    let span = UR - LL;
+    let mut g = Grid::new(span.x as usize + 1, span.y as usize + 1, '.');
+
+    // the head is drawn last, on top of the knots it overlaps
+    for (knot, new) in r.knots.iter().zip(DRAW.bytes()).rev() {
+        let p = *knot - LL;
+        if let (Ok(x), Ok(y)) = (usize::try_from(p.x), usize::try_from(p.y)) {
+            if let Some(c) = g.get_mut((x, y)) {
+                *c = new as char;
+            }
         }
     }
+    print!("{}", g);
 }
 
 #[derive(Clone, Debug)]
 struct Rope {
-    knots: Vec<Point>,
+    knots: Vec<Vec2>,
 }
 
 impl Default for Rope {
@@ -224,8 +65,8 @@ impl Default for Rope {
     }
 }
 
-const LL: Point = Point { x: -20, y: -20 };
-const UR: Point = Point { x: 20, y: 20 };
+const LL: Vec2 = Vec2::new(-20, -20);
+const UR: Vec2 = Vec2::new(20, 20);
 
 impl Rope {
     fn new_with_len(l: usize) -> Self {
@@ -237,47 +78,44 @@ impl Rope {
         rope
     }
 
-    pub fn step(&mut self, step: Direction) {
-        let mut it = self.knots.iter_mut().enumerate();
+    pub fn step(&mut self, step: Direction4) {
+        let mut it = self.knots.iter_mut();
 
-        let (_, head) = it.next().unwrap();
-        head.step_head(step);
+        let head = it.next().unwrap();
+        *head += step.unit();
         let mut prev = *head;
-        for (i, t) in it {
-            t.step_tail(step, &prev);
+        for t in it {
+            let diff = prev - *t;
+            // a knot that isn't touching the one before it any more moves one step
+            // towards it, diagonally if they aren't in the same row or column
+            if diff.chebyshev() > 1 {
+                *t += diff.signum();
+            }
             prev = *t;
         }
     }
 
-    fn get_head_mut(&mut self) -> &mut Point {
+    fn get_head_mut(&mut self) -> &mut Vec2 {
         self.knots.first_mut().expect("Tails vector is empty!")
     }
 
-    fn get_head(&self) -> &Point {
+    fn get_head(&self) -> &Vec2 {
         self.knots.first().expect("Tails vector is empty!")
     }
 
-    fn get_tail(&self) -> &Point {
+    fn get_tail(&self) -> &Vec2 {
         self.knots.last().expect("Tails vector is empty!")
     }
 }
 
 #[derive(Debug, Copy, Clone)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-#[derive(Debug, Copy, Clone)]
-struct Movement {
-    dir: Direction,
+pub struct Movement {
+    dir: Direction4,
     steps: usize,
 }
 
-fn parse_direction(i: &str) -> IResult<&str, Direction> {
-    use Direction::*;
+fn parse_direction(i: &str) -> IResult<&str, Direction4> {
+    use Direction4::*;
     let up = combinator::map(tag("U"), |_| Up);
     let down = combinator::map(tag("D"), |_| Down);
     let left = combinator::map(tag("L"), |_| Left);
@@ -297,20 +135,14 @@ fn parse_movement(i: &str) -> IResult<&str, Movement> {
     )(i)
 }
 
-fn series_of_motions(input: &str, length: usize) -> usize {
+fn series_of_motions(moves: &[Movement], length: usize) -> usize {
     let mut rope = Rope::new_with_len(length);
     let mut set = HashSet::new();
 
-    let it = input
-        .lines()
-        .map(|l| all_consuming(parse_movement)(l).unwrap().1);
-
-    let mut s = String::new();
-
-    for m in it {
+    for m in moves {
         for i in 0..m.steps {
             rope.step(m.dir);
-            set.insert(rope.get_tail().clone());
+            set.insert(*rope.get_tail());
         }
     }
 
@@ -324,7 +156,8 @@ mod tests {
 
     use assert_ok::assert_ok;
 
-    use super::{parse_movement, series_of_motions, Point, Rope};
+    use super::{parse_movement, series_of_motions, Day, Rope};
+    use crate::Solver;
 
     const CASE_1: &str = "R 4
 U 4
@@ -355,13 +188,13 @@ U 20";
     #[test_case(CASE_1 => 13)]
     #[test_case(CASE_2 => 88)]
     fn short_rope(input: &str) -> usize {
-        series_of_motions(input, 1)
+        series_of_motions(&Day.parse(input).unwrap(), 1)
     }
 
     #[test_case(CASE_1 => 1)]
     #[test_case(CASE_2 => 36)]
 
     fn long_rope(input: &str) -> usize {
-        series_of_motions(input, 9)
+        series_of_motions(&Day.parse(input).unwrap(), 9)
     }
 }
@@ -1,53 +1,3 @@
-// there is probably a less repititive way
-
-mod day_1;
-mod day_2;
-mod day_3;
-mod day_4;
-mod day_5;
-mod day_6;
-mod day_7;
-mod day_8;
-mod day_9;
-mod day_10;
-mod day_11;
-mod day_12;
-mod day_13;
-mod day_14;
-mod day_15;
-mod day_16;
-mod day_17;
-mod day_18;
-mod day_19;
-mod day_20;
-mod day_21;
-mod day_22;
-mod day_23;
-mod day_24;
-mod day_25;
-
-pub use day_1::Day as Day1;
-pub use day_2::Day as Day2;
-pub use day_3::Day as Day3;
-pub use day_4::Day as Day4;
-pub use day_5::Day as Day5;
-pub use day_6::Day as Day6;
-pub use day_7::Day as Day7;
-pub use day_8::Day as Day8;
-pub use day_9::Day as Day9;
-pub use day_10::Day as Day10;
-pub use day_11::Day as Day11;
-pub use day_12::Day as Day12;
-pub use day_13::Day as Day13;
-pub use day_14::Day as Day14;
-pub use day_15::Day as Day15;
-pub use day_16::Day as Day16;
-pub use day_17::Day as Day17;
-pub use day_18::Day as Day18;
-pub use day_19::Day as Day19;
-pub use day_20::Day as Day20;
-pub use day_21::Day as Day21;
-pub use day_22::Day as Day22;
-pub use day_23::Day as Day23;
-pub use day_24::Day as Day24;
-pub use day_25::Day as Day25;
\ No newline at end of file
+// `mod day_N;` for every day_N.rs in here plus the table of their registrations,
+// generated by build.rs
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
@@ -0,0 +1,254 @@
+//! Checking the solvers against known answers.
+//!
+//! The answers live in a TOML file (`answers.toml` by default) with one table per day:
+//!
+//! ```toml
+//! [day_5]
+//! part_1 = "CMZ"
+//! part_2 = "MCD"
+//! ```
+
+use std::{collections::BTreeMap, fmt, path::Path};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    runner::{Status, Summary},
+    Answer, AocError, Day, Part,
+};
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AnswerFile(BTreeMap<DayKey, Recorded>);
+
+/// Serialized as `day_N` but sorted by N
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct DayKey(usize);
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Recorded {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part_1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part_2: Option<String>,
+}
+
+impl Serialize for DayKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("day_{}", self.0))
+    }
+}
+
+impl<'de> Deserialize<'de> for DayKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.strip_prefix("day_")
+            .and_then(|d| d.parse().ok())
+            .map(DayKey)
+            .ok_or_else(|| de::Error::custom(format!("expected a key like day_1, got {s:?}")))
+    }
+}
+
+impl Recorded {
+    fn part(&self, part: Part) -> &Option<String> {
+        match part {
+            Part::Part1 => &self.part_1,
+            Part::Part2 => &self.part_2,
+        }
+    }
+
+    fn part_mut(&mut self, part: Part) -> &mut Option<String> {
+        match part {
+            Part::Part1 => &mut self.part_1,
+            Part::Part2 => &mut self.part_2,
+        }
+    }
+}
+
+impl AnswerFile {
+    /// Reads the answers, a file that doesn't exist yet has none
+    pub fn load(path: &Path) -> Result<Self, AocError> {
+        match std::fs::read_to_string(path) {
+            Ok(s) => Self::parse(&s).map_err(|e| AocError::file(path, e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(AocError::file(path, e)),
+        }
+    }
+
+    pub fn parse(s: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(s)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AocError> {
+        let s = toml::to_string_pretty(self).map_err(|e| AocError::file(path, e))?;
+        std::fs::write(path, s).map_err(|e| AocError::file(path, e))
+    }
+
+    pub fn get(&self, day: Day, part: Part) -> Option<&str> {
+        self.0.get(&DayKey(*day))?.part(part).as_deref()
+    }
+
+    pub fn set(&mut self, day: Day, part: Part, answer: &Answer) {
+        *self.0.entry(DayKey(*day)).or_default().part_mut(part) = Some(answer.to_string());
+    }
+
+    /// Stores every solved answer of a run, keeping the ones that weren't run
+    pub fn record(&mut self, summaries: &[Summary]) -> usize {
+        let mut recorded = 0;
+        for s in summaries {
+            if let Status::Solved { answer, .. } = &s.status {
+                self.set(s.day, s.part, answer);
+                recorded += 1;
+            }
+        }
+        recorded
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+        actual: Answer,
+    },
+    Error(AocError),
+    /// Solved, but there's nothing recorded to compare with
+    Missing(Answer),
+    /// No input or no solver, so nothing to check
+    Skipped,
+}
+
+impl Verdict {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Verdict::Fail { .. } | Verdict::Error(_))
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { expected, actual } => {
+                write!(
+                    f,
+                    "FAIL expected {expected:?}, got {:?}",
+                    actual.to_string()
+                )
+            }
+            Verdict::Error(e) => write!(f, "FAIL {e}"),
+            Verdict::Missing(actual) => write!(f, "missing, got {:?}", actual.to_string()),
+            Verdict::Skipped => write!(f, "skipped"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+    pub day: Day,
+    pub part: Part,
+    pub verdict: Verdict,
+}
+
+pub fn verify(summaries: &[Summary], answers: &AnswerFile) -> Vec<Check> {
+    summaries
+        .iter()
+        .map(|s| {
+            let expected = answers.get(s.day, s.part);
+            let verdict = match (&s.status, expected) {
+                (Status::Solved { answer, .. }, Some(e)) if answer == e => Verdict::Pass,
+                (Status::Solved { answer, .. }, Some(e)) => Verdict::Fail {
+                    expected: e.to_string(),
+                    actual: answer.clone(),
+                },
+                (Status::Solved { answer, .. }, None) => Verdict::Missing(answer.clone()),
+                (Status::Failed { error, .. }, _) => Verdict::Error(error.clone()),
+                (Status::NoInput | Status::Unimplemented, _) => Verdict::Skipped,
+            };
+            Check {
+                day: s.day,
+                part: s.part,
+                verdict,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE: &str = r#"[day_2]
+part_1 = "15"
+
+[day_10]
+part_1 = "13140"
+part_2 = """
+##..
+..##"""
+"#;
+
+    fn solved(day: usize, part: Part, answer: Answer) -> Summary {
+        Summary {
+            day: Day(day),
+            part,
+            status: Status::Solved {
+                answer,
+                timings: Default::default(),
+            },
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let answers = AnswerFile::parse(FILE).unwrap();
+        assert_eq!(answers.get(Day(2), Part::Part1), Some("15"));
+        assert_eq!(answers.get(Day(2), Part::Part2), None);
+        assert_eq!(answers.get(Day(10), Part::Part2), Some("##..\n..##"));
+
+        let s = toml::to_string(&answers).unwrap();
+        assert!(s.find("[day_2]").unwrap() < s.find("[day_10]").unwrap());
+        assert_eq!(AnswerFile::parse(&s).unwrap(), answers);
+    }
+
+    #[test]
+    fn verdicts() {
+        let answers = AnswerFile::parse(FILE).unwrap();
+        let summaries = [
+            solved(2, Part::Part1, 15usize.into()),
+            solved(2, Part::Part2, 12usize.into()),
+            solved(10, Part::Part1, 14000usize.into()),
+            solved(10, Part::Part2, Answer::image("##..\n..##\n")),
+            Summary {
+                day: Day(13),
+                part: Part::Part1,
+                status: Status::Unimplemented,
+            },
+        ];
+        let verdicts: Vec<_> = verify(&summaries, &answers)
+            .into_iter()
+            .map(|c| c.verdict)
+            .collect();
+        assert_eq!(
+            verdicts,
+            [
+                Verdict::Pass,
+                Verdict::Missing(12usize.into()),
+                Verdict::Fail {
+                    expected: "13140".into(),
+                    actual: 14000usize.into()
+                },
+                Verdict::Pass,
+                Verdict::Skipped,
+            ]
+        );
+    }
+
+    #[test]
+    fn record_keeps_others() {
+        let mut answers = AnswerFile::parse(FILE).unwrap();
+        answers.record(&[solved(2, Part::Part2, 12usize.into())]);
+        assert_eq!(answers.get(Day(2), Part::Part1), Some("15"));
+        assert_eq!(answers.get(Day(2), Part::Part2), Some("12"));
+    }
+}